@@ -2,7 +2,8 @@ use std::io::{stdout, Write};
 use crate::zone::Zone;
 use once_cell::sync::Lazy;
 use std::sync::Mutex;
-use crate::personnage::PNJ;
+use crate::personnage::{heure_jeu, PNJ};
+use chrono::Utc;
 
 /// Structure contenant la liste des notifications à afficher à l'utilisateur.
 pub struct ListeNotifications {
@@ -60,26 +61,31 @@ pub fn notifier(zone: &Zone, message: &str, tous_les_pnjs: &[PNJ]) {
 pub fn afficher_zone(zone: &Zone, tous_les_pnjs: &[PNJ]) {
     clear_terminal();
 
-    println!("\n🌍 Vous êtes dans la zone : {}", zone.nom);
+    println!("\n🌍 Vous êtes dans la zone : {} (🕒 {}h)", zone.nom, heure_jeu(Utc::now()));
     println!("------------------------------");
     println!("📜 Description : {}", zone.description);
+    if let Some(meteo) = &zone.meteo {
+        println!("🌦️ Météo : {}", meteo);
+    }
     println!("🚪 Sorties possibles :");
     for conn in &zone.connection {
-        println!("➡️  Vers '{}'", conn.direction);
+        if conn.exigences.is_empty() {
+            println!("➡️  Vers '{}'", conn.direction);
+        } else {
+            println!("➡️  Vers '{}' 🔒", conn.direction);
+        }
     }
     println!("Il y a {} coffres dans la zone", zone.compter_coffre());
     
     let pnjs_dans_la_zone: Vec<&PNJ> = tous_les_pnjs
         .iter()
-        .filter(|p| p.zone_id == zone.id as u32)
+        .filter(|p| p.zone_id == zone.id as u32 && p.personnage.est_vivant)
         .collect();
 
     if !pnjs_dans_la_zone.is_empty() {
         println!("👥 PNJ présents :");
         for pnj in pnjs_dans_la_zone {
-            if pnj.personnage.est_vivant {
-                println!("- {}", pnj.personnage.nom);
-            }
+            println!("- {}", pnj.personnage.nom);
         }
     }
     println!("------------------------------");
@@ -105,6 +111,7 @@ pub fn afficher_zone(zone: &Zone, tous_les_pnjs: &[PNJ]) {
 ///
 /// # Retour
 /// Renvoie le choix validé de l'utilisateur sous forme de `String`.
+#[allow(clippy::ptr_arg)]
 pub fn faire_choix(message: & str, choixpossibles: &Vec<String>) -> String {
     loop {
         println!("{}", message);
@@ -127,4 +134,11 @@ pub fn faire_choix(message: & str, choixpossibles: &Vec<String>) -> String {
             println!("❌ Choix invalide. Veuillez réessayer !\n");
         }
     }
-}
\ No newline at end of file
+}
+/// Formate un poids exprimé en grammes en kilogrammes lisibles (ex. : `12.5 kg`).
+///
+/// # Arguments
+/// * `grammes` - Le poids à formater, en grammes.
+pub fn formater_poids(grammes: u32) -> String {
+    format!("{:.1} kg", grammes as f32 / 1000.0)
+}
@@ -0,0 +1,259 @@
+use std::error::Error;
+use std::fs;
+use serde::{Serialize, Deserialize};
+use crate::affichage;
+use crate::objet::OBJETS_DISPONIBLES;
+use crate::personnage::{Personnage, PNJ};
+use crate::zone::Zone;
+
+/// Quantité d'un objet consommée ou produite par une recette.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Ingredient {
+    /// Identifiant de l'objet dans `objet.json`.
+    pub objet_id: u8,
+    /// Nombre d'exemplaires.
+    pub nombre: u8,
+}
+
+/// Recette de fabrication, chargée depuis `recette.json`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Recette {
+    /// Identifiant unique de la recette.
+    pub id: u32,
+    /// Nom affiché dans le menu de fabrication.
+    pub nom: String,
+    /// Objets consommés.
+    pub ingredients: Vec<Ingredient>,
+    /// Objet fabriqué.
+    pub produit: Ingredient,
+    /// PNJ (par exemple le forgeron) qui doit être présent et vivant dans la zone.
+    #[serde(default)]
+    pub pnj_id: Option<u32>,
+    /// Zone où la recette doit être réalisée.
+    #[serde(default)]
+    pub zone_id: Option<u8>,
+}
+
+/// Renvoie le nom d'un objet du catalogue, ou son identifiant s'il est inconnu.
+fn nom_objet(objet_id: u8) -> String {
+    OBJETS_DISPONIBLES.read().unwrap()
+        .get(&objet_id)
+        .map(|o| o.nom.clone())
+        .unwrap_or_else(|| format!("ID {}", objet_id))
+}
+
+impl Recette {
+    /// Charge la liste des recettes depuis un fichier JSON.
+    ///
+    /// # Arguments
+    /// * `fichier` - Chemin du fichier JSON.
+    pub fn charger(fichier: &str) -> Result<Vec<Recette>, Box<dyn Error>> {
+        let contenu = fs::read_to_string(fichier)?;
+        Ok(serde_json::from_str(&contenu)?)
+    }
+
+    /// Décrit la recette : ingrédients, produit et lieu requis.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur, qui ne connaît que le nom des zones visitées.
+    /// * `pnjs` - Tous les PNJs du jeu, pour nommer l'artisan requis.
+    /// * `zones` - Toutes les zones du jeu, pour nommer la zone requise.
+    pub fn description(&self, joueur: &Personnage, pnjs: &[PNJ], zones: &[Zone]) -> String {
+        let ingredients: Vec<String> = self.ingredients.iter()
+            .map(|i| format!("{} x{}", nom_objet(i.objet_id), i.nombre))
+            .collect();
+        let mut description = format!("{} -> {} x{}", ingredients.join(" + "), nom_objet(self.produit.objet_id), self.produit.nombre);
+        if let Some(pnj) = self.pnj_id.and_then(|id| pnjs.iter().find(|p| p.personnage.id == id)) {
+            description.push_str(&format!(" (avec {})", pnj.personnage.nom));
+        }
+        if let Some(zone_id) = self.zone_id {
+            let nom = zones.iter().find(|z| z.id == zone_id)
+                .map(|z| z.nom_pour(joueur))
+                .unwrap_or_else(|| zone_id.to_string());
+            description.push_str(&format!(" (dans la zone {})", nom));
+        }
+        description
+    }
+
+    /// Vérifie que le joueur peut réaliser la recette là où il se trouve.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur.
+    /// * `zone` - La zone où se trouve le joueur.
+    /// * `pnjs` - Tous les PNJs du jeu.
+    ///
+    /// # Retour
+    /// `Err` avec l'explication à afficher si la recette est irréalisable.
+    pub fn verifier(&self, joueur: &Personnage, zone: &Zone, pnjs: &[PNJ]) -> Result<(), String> {
+        if let Some(zone_id) = self.zone_id {
+            if zone.id != zone_id {
+                return Err("Cette recette ne peut pas être réalisée ici.".to_string());
+            }
+        }
+        if let Some(pnj_id) = self.pnj_id {
+            let present = pnjs.iter().any(|p| p.personnage.id == pnj_id && p.zone_id == zone.id as u32 && p.personnage.est_vivant);
+            if !present {
+                let nom = pnjs.iter().find(|p| p.personnage.id == pnj_id)
+                    .map(|p| p.personnage.nom.clone())
+                    .unwrap_or_else(|| "un artisan".to_string());
+                return Err(format!("Il vous faut l'aide de {}.", nom));
+            }
+        }
+        for ingredient in &self.ingredients {
+            let possedes: u32 = joueur.inventaire.objets.iter()
+                .filter(|o| o.objet_id == ingredient.objet_id)
+                .map(|o| o.nombre as u32)
+                .sum();
+            if possedes < ingredient.nombre as u32 {
+                return Err(format!("Il vous manque {} ({}/{}).", nom_objet(ingredient.objet_id), possedes, ingredient.nombre));
+            }
+        }
+        Ok(())
+    }
+
+    /// Réalise la recette : consomme les ingrédients et ajoute le produit à l'inventaire.
+    ///
+    /// Rien n'est consommé si le produit ne trouve pas de place dans l'inventaire,
+    /// une fois les ingrédients retirés, ou s'il est trop lourd pour le joueur.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur.
+    /// * `zone` - La zone où se trouve le joueur.
+    /// * `pnjs` - Tous les PNJs du jeu.
+    pub fn fabriquer(&self, joueur: &mut Personnage, zone: &Zone, pnjs: &[PNJ]) -> Result<(), String> {
+        self.verifier(joueur, zone, pnjs)?;
+        let mut inventaire = joueur.inventaire.clone();
+        for ingredient in &self.ingredients {
+            for _ in 0..ingredient.nombre {
+                inventaire.retirer_par_id(ingredient.objet_id);
+            }
+        }
+        if inventaire.ajouter_objets(self.produit.objet_id, self.produit.nombre).is_err() {
+            return Err("Pas assez de place dans votre inventaire !".to_string());
+        }
+        let supplement = inventaire.poids_total().saturating_sub(joueur.inventaire.poids_total());
+        if !joueur.peut_porter(supplement) {
+            return Err("C'est trop lourd, vous ne pouvez pas porter autant !".to_string());
+        }
+        joueur.inventaire = inventaire;
+        Ok(())
+    }
+}
+
+/// Affiche les recettes et laisse le joueur en réaliser une.
+///
+/// # Arguments
+/// * `recettes` - Toutes les recettes du jeu.
+/// * `joueur` - Le personnage du joueur.
+/// * `zones` - Toutes les zones du jeu.
+/// * `current_zone_index` - Indice de la zone où se trouve le joueur.
+/// * `pnjs` - Tous les PNJs du jeu.
+pub fn menu_artisanat(recettes: &[Recette], joueur: &mut Personnage, zones: &[Zone], current_zone_index: usize, pnjs: &[PNJ]) {
+    let zone = &zones[current_zone_index];
+    if recettes.is_empty() {
+        affichage::notifier(zone, "Vous ne connaissez aucune recette.", pnjs);
+        return;
+    }
+    let mut message = String::from("🔨 Recettes de fabrication :\n");
+    for (index, recette) in recettes.iter().enumerate() {
+        let statut = if recette.verifier(joueur, zone, pnjs).is_ok() { "✅" } else { "❌" };
+        message.push_str(&format!("  {} : {} {} — {}\n", index + 1, statut, recette.nom, recette.description(joueur, pnjs, zones)));
+    }
+    message.push_str("Quelle recette voulez-vous réaliser ?");
+    let choix = affichage::faire_choix(&message, &(1..=recettes.len()).map(|i| i.to_string()).collect());
+    let Some(recette) = choix.parse::<usize>().ok().and_then(|i| recettes.get(i - 1)) else {
+        return;
+    };
+    let msg = match recette.fabriquer(joueur, zone, pnjs) {
+        Ok(()) => format!("✅ Vous fabriquez {} x{}.", nom_objet(recette.produit.objet_id), recette.produit.nombre),
+        Err(e) => format!("❌ {}", e),
+    };
+    affichage::notifier(zone, &msg, pnjs);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventaire::ObjetInventaire;
+    use crate::outils_test::{catalogue_test, joueur_test, zone_test, CORDE, FIL, ROCHER};
+
+    /// Teste qu'une recette consomme ses ingrédients et produit l'objet attendu,
+    /// et que rien n'est consommé quand elle échoue.
+    #[test]
+    fn test_fabriquer() {
+        catalogue_test();
+        let recette = Recette {
+            id: 1,
+            nom: "Corde".to_string(),
+            ingredients: vec![Ingredient { objet_id: FIL, nombre: 3 }],
+            produit: Ingredient { objet_id: CORDE, nombre: 1 },
+            pnj_id: None,
+            zone_id: Some(2),
+        };
+        let mut joueur = joueur_test(vec![ObjetInventaire { nombre: 4, objet_id: FIL, instance: None }], 1);
+
+        assert!(recette.fabriquer(&mut joueur, &zone_test(1), &[]).is_err());
+        // L'unique emplacement est encore occupé par le fil restant
+        assert_eq!(recette.fabriquer(&mut joueur, &zone_test(2), &[]), Err("Pas assez de place dans votre inventaire !".to_string()));
+        assert_eq!(joueur.inventaire.objets[0].nombre, 4);
+
+        joueur.inventaire.taille = 2;
+        recette.fabriquer(&mut joueur, &zone_test(2), &[]).unwrap();
+        let nombre = |id: u8| joueur.inventaire.objets.iter().filter(|o| o.objet_id == id).map(|o| o.nombre).sum::<u8>();
+        assert_eq!((nombre(FIL), nombre(CORDE)), (1, 1));
+        assert!(recette.verifier(&joueur, &zone_test(2), &[]).is_err());
+
+        let zones = [Zone { nom: "Forge".to_string(), ..zone_test(2) }];
+        // La zone requise reste inconnue tant que le joueur ne l'a pas visitée
+        assert_eq!(recette.description(&joueur, &[], &zones), "Fil x3 -> Corde x1 (dans la zone inconnue)");
+        joueur.progression.visiter_zone(2);
+        assert_eq!(recette.description(&joueur, &[], &zones), "Fil x3 -> Corde x1 (dans la zone Forge)");
+    }
+
+    /// Teste qu'un produit plus lourd que ses ingrédients n'est pas fabriqué
+    /// si le joueur ne peut pas le porter.
+    #[test]
+    fn test_fabriquer_trop_lourd() {
+        catalogue_test();
+        let recette = Recette {
+            id: 2,
+            nom: "Rocher".to_string(),
+            ingredients: vec![Ingredient { objet_id: FIL, nombre: 1 }],
+            produit: Ingredient { objet_id: ROCHER, nombre: 1 },
+            pnj_id: None,
+            zone_id: None,
+        };
+        // 10 de force portent 1 kg, 1,5 kg en surcharge : le rocher pèse 4 kg
+        let mut joueur = joueur_test(vec![ObjetInventaire { nombre: 1, objet_id: FIL, instance: None }], 2);
+        assert_eq!(recette.fabriquer(&mut joueur, &zone_test(1), &[]), Err("C'est trop lourd, vous ne pouvez pas porter autant !".to_string()));
+        assert_eq!(joueur.inventaire.objets[0].objet_id, FIL);
+        joueur.force = 100;
+        recette.fabriquer(&mut joueur, &zone_test(1), &[]).unwrap();
+        assert_eq!(joueur.inventaire.objets[0].objet_id, ROCHER);
+    }
+
+    /// Vérifie les recettes du jeu : objets existants et artisans exigés présents.
+    #[test]
+    fn test_recettes_du_jeu() {
+        crate::moteur::charger_objets().unwrap();
+        let recettes = Recette::charger("src/json/recette.json").unwrap();
+        let pnjs = PNJ::charger_pnj("src/json/pnj.json").unwrap();
+        let objets = OBJETS_DISPONIBLES.read().unwrap();
+        for recette in &recettes {
+            assert!(objets.contains_key(&recette.produit.objet_id));
+            assert!(recette.ingredients.iter().all(|i| objets.contains_key(&i.objet_id)));
+            if let Some(pnj_id) = recette.pnj_id {
+                assert!(pnjs.iter().any(|p| p.personnage.id == pnj_id), "PNJ {} inconnu pour {}", pnj_id, recette.nom);
+            }
+        }
+        drop(objets);
+        let katana = recettes.iter().find(|r| r.produit.objet_id == 2).unwrap();
+        let mut forgeron = pnjs.into_iter().find(|p| Some(p.personnage.id) == katana.pnj_id).unwrap();
+        let joueur = joueur_test(vec![ObjetInventaire { nombre: 1, objet_id: 1, instance: None }, ObjetInventaire { nombre: 3, objet_id: 16, instance: None }], 5);
+        forgeron.zone_id = 4;
+        assert!(katana.verifier(&joueur, &zone_test(4), std::slice::from_ref(&forgeron)).is_ok());
+        forgeron.zone_id = 5;
+        let erreur = katana.verifier(&joueur, &zone_test(4), std::slice::from_ref(&forgeron)).unwrap_err();
+        assert!(erreur.contains(&forgeron.personnage.nom));
+    }
+}
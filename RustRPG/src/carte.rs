@@ -0,0 +1,211 @@
+use std::collections::{HashMap, VecDeque};
+use crate::personnage::{Personnage, PNJ};
+use crate::zone::Zone;
+
+/// Largeur d'une case de zone, par exemple `[@ 1!]`.
+const LARGEUR_CASE: usize = 6;
+/// Liaison dessinée entre deux zones voisines sur une même ligne.
+const LIAISON_HORIZONTALE: &str = "--";
+
+/// Décalage sur la grille correspondant à une direction de connexion.
+///
+/// # Retour
+/// `None` si la direction ne correspond à aucun point cardinal.
+fn decalage(direction: &str) -> Option<(i32, i32)> {
+    match direction {
+        "nord" => Some((0, -1)),
+        "sud" => Some((0, 1)),
+        "est" => Some((1, 0)),
+        "ouest" => Some((-1, 0)),
+        _ => None,
+    }
+}
+
+/// Place les zones sur une grille en parcourant les connexions depuis une zone de départ.
+///
+/// Une zone n'est placée qu'une fois, à la première position trouvée ; une case
+/// déjà occupée par une autre zone n'est pas réutilisée.
+///
+/// # Arguments
+/// * `zones` - Toutes les zones du jeu.
+/// * `depart` - Index de la zone placée en `(0, 0)`.
+///
+/// # Retour
+/// La position `(colonne, ligne)` de chaque zone atteinte, indexée par identifiant.
+pub fn placer_zones(zones: &[Zone], depart: usize) -> HashMap<u8, (i32, i32)> {
+    let mut positions = HashMap::new();
+    let mut a_traiter = VecDeque::new();
+    positions.insert(zones[depart].id, (0, 0));
+    a_traiter.push_back(depart);
+
+    while let Some(index) = a_traiter.pop_front() {
+        let (x, y) = positions[&zones[index].id];
+        for conn in &zones[index].connection {
+            let (Some((dx, dy)), Ok(id_dest)) = (decalage(&conn.direction), conn.id_dest.parse::<u8>()) else {
+                continue;
+            };
+            let position = (x + dx, y + dy);
+            if positions.contains_key(&id_dest) || positions.values().any(|p| *p == position) {
+                continue;
+            }
+            if let Some(index_dest) = zones.iter().position(|z| z.id == id_dest) {
+                positions.insert(id_dest, position);
+                a_traiter.push_back(index_dest);
+            }
+        }
+    }
+    positions
+}
+
+/// Indique si le joueur connaît une connexion entre deux zones, dans un sens ou dans l'autre.
+fn liaison_connue(a: &Zone, b: &Zone, joueur: &Personnage) -> bool {
+    let progression = &joueur.progression;
+    let vers = |depuis: &Zone, id: u8| {
+        progression.connait_connexion(depuis.id, id) && depuis.connection.iter().any(|c| c.id_dest == id.to_string())
+    };
+    vers(a, b.id) || vers(b, a.id)
+}
+
+/// Dessine la case d'une zone : `[` + état + identifiant + contenu + `]`.
+///
+/// L'état vaut `@` pour la zone courante, `$` pour une zone à acheter et `*` pour
+/// une zone visitée ; le contenu vaut `!` si un mob s'y trouve et `P` si des PNJs
+/// y sont présents. Une zone aperçue mais jamais visitée garde son identifiant
+/// et son contenu secrets.
+fn dessiner_case(zone: &Zone, courante: bool, joueur: &Personnage, pnjs: &[PNJ]) -> String {
+    if !courante && !joueur.progression.a_visite(zone.id) {
+        return format!("[{}?? ]", if zone.ouvert { ' ' } else { '$' });
+    }
+    let etat = if courante {
+        '@'
+    } else if !zone.ouvert {
+        '$'
+    } else if joueur.progression.a_visite(zone.id) {
+        '*'
+    } else {
+        ' '
+    };
+    let contenu = if zone.mob.is_some() {
+        '!'
+    } else if pnjs.iter().any(|p| p.zone_id == zone.id as u32 && p.personnage.est_vivant) {
+        'P'
+    } else {
+        ' '
+    };
+    format!("[{}{:>2}{}]", etat, zone.id, contenu)
+}
+
+/// Construit la carte ASCII du monde, centrée sur le graphe des connexions de la zone courante,
+/// suivie d'une légende.
+///
+/// Seules les zones connues du joueur apparaissent : celles qu'il a visitées et
+/// celles vers lesquelles mène une sortie qu'il a déjà vue.
+///
+/// # Arguments
+/// * `zones` - Toutes les zones du jeu.
+/// * `current_zone_index` - Index de la zone où se trouve le joueur.
+/// * `joueur` - Le personnage du joueur, pour les zones visitées.
+/// * `pnjs` - Tous les PNJs du jeu.
+///
+/// # Retour
+/// Le texte de la carte, prêt à être affiché.
+pub fn dessiner_carte(zones: &[Zone], current_zone_index: usize, joueur: &Personnage, pnjs: &[PNJ]) -> String {
+    let courante = zones[current_zone_index].id;
+    let mut positions = placer_zones(zones, current_zone_index);
+    positions.retain(|id, _| *id == courante || joueur.progression.connait_zone(*id));
+    let min_x = positions.values().map(|p| p.0).min().unwrap_or(0);
+    let max_x = positions.values().map(|p| p.0).max().unwrap_or(0);
+    let min_y = positions.values().map(|p| p.1).min().unwrap_or(0);
+    let max_y = positions.values().map(|p| p.1).max().unwrap_or(0);
+    let zone_en = |x: i32, y: i32| {
+        positions.iter()
+            .find(|(_, p)| **p == (x, y))
+            .and_then(|(id, _)| zones.iter().find(|z| z.id == *id))
+    };
+    let vide = " ".repeat(LARGEUR_CASE);
+    let espace = " ".repeat(LIAISON_HORIZONTALE.len());
+
+    let mut carte = String::from("🗺️  Carte du monde\n\n");
+    for y in min_y..=max_y {
+        let mut ligne = String::new();
+        let mut liaisons = String::new();
+        for x in min_x..=max_x {
+            let zone = zone_en(x, y);
+            match zone {
+                Some(z) => ligne.push_str(&dessiner_case(z, z.id == courante, joueur, pnjs)),
+                None => ligne.push_str(&vide),
+            }
+            if x < max_x {
+                let reliee = matches!((zone, zone_en(x + 1, y)), (Some(a), Some(b)) if liaison_connue(a, b, joueur));
+                ligne.push_str(if reliee { LIAISON_HORIZONTALE } else { &espace });
+            }
+            if y < max_y {
+                let reliee = matches!((zone, zone_en(x, y + 1)), (Some(a), Some(b)) if liaison_connue(a, b, joueur));
+                let milieu = LARGEUR_CASE / 2;
+                liaisons.push_str(&" ".repeat(milieu));
+                liaisons.push(if reliee { '|' } else { ' ' });
+                liaisons.push_str(&" ".repeat(LARGEUR_CASE - milieu - 1));
+                if x < max_x {
+                    liaisons.push_str(&espace);
+                }
+            }
+        }
+        carte.push_str(ligne.trim_end());
+        carte.push('\n');
+        if y < max_y {
+            carte.push_str(liaisons.trim_end());
+            carte.push('\n');
+        }
+    }
+
+    carte.push_str("\nLégende : @ vous êtes ici, * visitée, $ à acheter, ?? inexplorée, ! ennemi, P PNJs\n");
+    let mut ids: Vec<&u8> = positions.keys().collect();
+    ids.sort();
+    for id in ids {
+        if let Some(zone) = zones.iter().find(|z| z.id == *id && (z.id == courante || joueur.progression.a_visite(z.id))) {
+            if zone.ouvert {
+                carte.push_str(&format!("  {:>2} : {}\n", zone.id, zone.nom));
+            } else {
+                carte.push_str(&format!("  {:>2} : {} (fermée, {} pièces)\n", zone.id, zone.nom, zone.prix));
+            }
+        }
+    }
+    carte
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moteur::{charger_objets, charger_zones};
+    use crate::outils_test::joueur_test;
+
+    /// Vérifie la disposition des zones du jeu et les marqueurs de la carte.
+    #[test]
+    fn test_carte_du_monde() {
+        charger_objets().unwrap();
+        let mut zones = charger_zones().unwrap();
+        zones.sort_by_key(|z| z.id);
+        let positions = placer_zones(&zones, 0);
+        assert_eq!(positions.len(), zones.len());
+        assert_eq!(positions[&1], (0, 0));
+        assert_eq!(positions[&2], (0, -1));
+        assert_eq!(positions[&3], (-1, 0));
+        assert_eq!(positions[&9], (-2, -2));
+
+        let joueur = joueur_test(vec![], 5);
+        // Sans exploration, seule la zone courante apparaît
+        let carte = dessiner_carte(&zones, 0, &joueur, &[]);
+        assert!(carte.contains("[@ 1 ]"));
+        assert!(!carte.contains("[$??"));
+
+        let mut joueur = joueur;
+        joueur.progression.decouvrir_zone(1, &zones[0].destinations());
+        joueur.progression.decouvrir_zone(2, &zones[1].destinations());
+        zones[1].mob = Some(1);
+        let carte = dessiner_carte(&zones, 0, &joueur, &[]);
+        assert!(carte.contains("[* 2!]"));
+        assert!(carte.contains("[$?? ]--[@ 1 ]"));
+        assert!(!carte.contains("Plaines du Vent Solitaire"));
+        assert!(!carte.contains("[$?? ]--[$?? ]"));
+    }
+}
@@ -0,0 +1,55 @@
+use std::error::Error;
+use std::fs;
+use serde::{Serialize, Deserialize};
+use crate::inventaire::ObjetInventaire;
+use crate::personnage::Attributs;
+
+/// Archétype proposé à la création d'un personnage, chargé depuis `classe.json`.
+///
+/// La classe fixe les statistiques de départ, l'équipement, l'argent et la zone
+/// de départ ; le joueur répartit ensuite `points_a_repartir` points lui-même.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Classe {
+    /// Nom de la classe (ex. : "Guerrier").
+    pub nom: String,
+    /// Description affichée au moment du choix.
+    pub description: String,
+    /// Force de départ.
+    pub force: u8,
+    /// Attributs de départ.
+    pub attributs: Attributs,
+    /// Argent de départ.
+    pub argent: u32,
+    /// Objets de départ ; armes et équipements sont portés directement si possible.
+    #[serde(default)]
+    pub equipement: Vec<ObjetInventaire>,
+    /// Identifiant de la zone où commence le personnage.
+    pub zone_depart: u8,
+    /// Points à répartir par le joueur à la création.
+    #[serde(default)]
+    pub points_a_repartir: u32,
+}
+
+impl Classe {
+    /// Charge la liste des classes depuis un fichier JSON.
+    ///
+    /// # Arguments
+    /// * `fichier` - Chemin du fichier JSON.
+    pub fn charger(fichier: &str) -> Result<Vec<Classe>, Box<dyn Error>> {
+        let contenu = fs::read_to_string(fichier)?;
+        Ok(serde_json::from_str(&contenu)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charger_classes() {
+        let classes = Classe::charger("src/json/classe.json").unwrap();
+        assert!(classes.len() >= 3);
+        assert!(classes.iter().any(|c| c.nom == "Guerrier"));
+        assert!(classes.iter().all(|c| c.attributs.agilite <= 100 && c.attributs.charisme <= 100));
+    }
+}
@@ -2,7 +2,16 @@ use crate::inventaire::Inventaire;
 use crate::affichage;
 use crate::zone::Zone;
 use crate::personnage::PNJ;
-use crate::personnage::Personnage;
+use crate::personnage::{Personnage, ResultatBlessure};
+use rand::Rng;
+use rand::prelude::IndexedRandom;
+
+/// Identifiant de la clé ordinaire, qui ouvre les coffres sans clé spécifique.
+pub const ID_CLE: u8 = 12;
+/// Identifiant du crochet de serrurier.
+pub const ID_CROCHET: u8 = 14;
+/// Probabilité qu'un crochet se brise lors d'une tentative ratée.
+const PROBA_CASSE_CROCHET: f64 = 0.3;
 
 /// Représente un coffre contenant des objets dans une zone du jeu.
 /// Un coffre peut être visible ou non, ouvert ou fermé, et possède un inventaire propre.
@@ -20,53 +29,178 @@ pub struct Coffre {
     pub inventaire: Inventaire,
     /// Indique si le coffre est visible dans la zone (`true`) ou caché (`false`).
     pub visible: bool,
+    /// Difficulté à trouver le coffre lorsqu'il est caché (0 à 100).
+    pub difficulte: u8,
+    /// Objet indispensable pour découvrir le coffre caché (ex. : une lampe torche).
+    pub outil: Option<u8>,
+    /// Difficulté de la serrure pour le crochetage (0 à 100).
+    pub serrure: u8,
+    /// Identifiant de la clé qui ouvre ce coffre (`ID_CLE` par défaut).
+    pub cle: u8,
+    /// Dégâts infligés par le piège du coffre, 0 s'il n'est pas piégé.
+    pub piege: u32,
 }
 
 impl Coffre {
+    /// Calcule la probabilité de découvrir ce coffre caché lors d'une fouille.
+    ///
+    /// La chance de base est de 50 % plus l'écart entre la perception et la difficulté,
+    /// augmentée de 10 % par fouille infructueuse déjà faite dans la zone.
+    ///
+    /// # Arguments
+    /// * `perception` - La perception du joueur qui fouille.
+    /// * `tentatives` - Le nombre de fouilles infructueuses précédentes.
+    ///
+    /// # Retour
+    /// Une probabilité comprise entre 0,05 et 1.
+    pub fn chance_decouverte(&self, perception: u8, tentatives: u8) -> f64 {
+        let score = perception as f64 - self.difficulte as f64 + 50.0 + 10.0 * tentatives as f64;
+        (score / 100.0).clamp(0.05, 1.0)
+    }
+
+    /// Calcule la probabilité de crocheter la serrure du coffre.
+    ///
+    /// # Arguments
+    /// * `agilite` - L'agilité du joueur qui crochète.
+    ///
+    /// # Retour
+    /// Une probabilité comprise entre 0,05 et 0,95.
+    pub fn chance_crochetage(&self, agilite: u8) -> f64 {
+        ((agilite as f64 - self.serrure as f64 + 50.0) / 100.0).clamp(0.05, 0.95)
+    }
+
     /// Tente d'ouvrir le coffre.
     ///
-    /// Si le coffre est fermé, demande au joueur s’il souhaite utiliser une clé
-    /// (objet d’ID 12) pour l’ouvrir. Si la clé est disponible dans l'inventaire du joueur,
-    /// elle est consommée et le coffre est ouvert.
+    /// Si le coffre est fermé, le joueur peut utiliser la clé propre au coffre
+    /// (la clé ordinaire d'ID 12 par défaut), qui est consommée, ou tenter de
+    /// crocheter la serrure avec un crochet, qui peut se briser en cas d'échec.
+    /// Une fois la serrure ouverte, un coffre piégé blesse le joueur, sauf s'il
+    /// remarque le piège et parvient à le désamorcer.
     ///
     /// Retourne `Some(())` si le coffre a été ouvert avec succès, ou s’il l’était déjà.
-    /// Retourne `None` si l’utilisateur annule ou s’il n’a pas de clé.
+    /// Retourne `None` si l’utilisateur annule, échoue ou meurt du piège.
     ///
     /// # Arguments
     /// - `zone` : Référence à la zone actuelle (pour les notifications).
     /// - `joueur` : Le personnage joueur interagissant avec le coffre.
     /// - `pnjs` : Liste des PNJs présents dans la zone.
-    pub fn ouvrir(&mut self, zone: &Zone, joueur: &mut Personnage, pnjs: &Vec<PNJ>) -> Option<()>{
+    pub fn ouvrir(&mut self, zone: &Zone, joueur: &mut Personnage, pnjs: &[PNJ]) -> Option<()>{
         if !self.ouvert {
             let choix = affichage::faire_choix(
-                "Ce coffre est fermé voulez-vous utiliser une clé pour l'ouvrir ? (oui/non)",
-                &vec!["oui".to_string(), "non".to_string()]
+                "Ce coffre est fermé. Utiliser une clé, le crocheter ou partir ? (cle/crocheter/non)",
+                &vec!["cle".to_string(), "crocheter".to_string(), "non".to_string()]
             );
             match choix.as_str() {
-                "oui" => {
-                    if !joueur.inventaire.retirer_par_id(12) {
-                        affichage::notifier(zone, "❌ Vous n'avez pas de clé !", pnjs);
+                "cle" => {
+                    if !joueur.inventaire.retirer_par_id(self.cle) {
+                        let msg = if self.cle == ID_CLE {
+                            "❌ Vous n'avez pas de clé !"
+                        } else {
+                            "❌ Aucune de vos clés ne rentre dans cette serrure !"
+                        };
+                        affichage::notifier(zone, msg, pnjs);
                         return None;
                     }
                     self.ouvert = true;
                     affichage::notifier(zone, "🔑 Vous utilisez une clé et ouvrez le coffre !", pnjs);
                 }
+                "crocheter" => {
+                    if !self.crocheter(zone, joueur, pnjs) {
+                        return None;
+                    }
+                }
                 _ => {
                     println!("Le coffre reste verrouillé !");
                     return None;
                 }
             }
         }
+        if self.piege > 0 && !self.eviter_piege(zone, joueur, pnjs) {
+            return None;
+        }
         println!("Ouverture du coffre ! ");
         Some(())
     }
 
+    /// Tente de crocheter la serrure du coffre avec un crochet de l'inventaire.
+    ///
+    /// # Retour
+    /// `true` si la serrure a cédé.
+    fn crocheter(&mut self, zone: &Zone, joueur: &mut Personnage, pnjs: &[PNJ]) -> bool {
+        if !joueur.inventaire.objets.iter().any(|o| o.objet_id == ID_CROCHET) {
+            affichage::notifier(zone, "❌ Vous n'avez pas de crochet !", pnjs);
+            return false;
+        }
+        let mut rng = rand::rng();
+        if rng.random_bool(self.chance_crochetage(joueur.attributs.agilite)) {
+            self.ouvert = true;
+            affichage::notifier(zone, "🔓 La serrure cède sous votre crochet !", pnjs);
+            return true;
+        }
+        if rng.random_bool(PROBA_CASSE_CROCHET) {
+            joueur.inventaire.retirer_par_id(ID_CROCHET);
+            affichage::notifier(zone, "❌ Votre crochet se brise dans la serrure !", pnjs);
+        } else {
+            affichage::notifier(zone, "❌ La serrure résiste.", pnjs);
+        }
+        false
+    }
 
+    /// Gère le piège d'un coffre au moment de l'ouvrir.
+    ///
+    /// Le joueur remarque le piège selon sa perception ; il peut alors tenter de le
+    /// désamorcer (agilité) ou renoncer. S'il ne le remarque pas, ou rate le
+    /// désamorçage, le piège se déclenche.
+    ///
+    /// # Retour
+    /// `true` si le joueur peut ouvrir le coffre.
+    fn eviter_piege(&mut self, zone: &Zone, joueur: &mut Personnage, pnjs: &[PNJ]) -> bool {
+        let mut rng = rand::rng();
+        let perception = joueur.attributs.perception as f64 / 100.0;
+        if rng.random_bool(perception.clamp(0.0, 1.0)) {
+            let choix = affichage::faire_choix(
+                "⚠️ Vous remarquez un mécanisme suspect. Tenter de le désamorcer ? (oui/non)",
+                &vec!["oui".to_string(), "non".to_string()]
+            );
+            if choix != "oui" {
+                println!("Vous laissez le coffre piégé tranquille.");
+                return false;
+            }
+            if rng.random_bool(self.chance_crochetage(joueur.attributs.agilite)) {
+                self.piege = 0;
+                affichage::notifier(zone, "✅ Vous désamorcez le piège.", pnjs);
+                return true;
+            }
+        }
+        let resultat = self.declencher_piege(joueur);
+        affichage::notifier(zone, "💥 Le coffre était piégé !", pnjs);
+        !matches!(resultat, Some(ResultatBlessure::Mort))
+    }
+
+    /// Déclenche le piège du coffre sur une partie du corps choisie au hasard.
+    /// Le piège est ensuite désarmé.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage qui subit le piège.
+    ///
+    /// # Retour
+    /// Le résultat de la blessure, ou `None` si le coffre n'était pas piégé.
+    pub fn declencher_piege(&mut self, joueur: &mut Personnage) -> Option<ResultatBlessure> {
+        if self.piege == 0 {
+            return None;
+        }
+        let degats = self.piege;
+        self.piege = 0;
+        let partie = joueur.parties_du_corps.choose(&mut rand::rng())?.nom().to_string();
+        println!("Le piège vous blesse au niveau de : {}", partie);
+        Some(joueur.gerer_blessure(&partie, degats))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::outils_test::joueur_test;
 
     #[test]
     fn test_coffre_creation() {
@@ -77,8 +211,43 @@ mod tests {
             _description: "Un coffre".to_string(),
             inventaire: Inventaire { taille: 1, objets: vec![] },
             visible: true,
+            difficulte: 40,
+            outil: None,
+            serrure: 50,
+            cle: ID_CLE,
+            piege: 0,
         };
         assert_eq!(coffre._id, 1);
         assert!(!coffre.ouvert);
+        assert!((coffre.chance_decouverte(50, 0) - 0.6).abs() < 1e-9);
+        assert!((coffre.chance_decouverte(50, 2) - 0.8).abs() < 1e-9);
+        assert_eq!(coffre.chance_decouverte(100, 5), 1.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_crochetage_et_piege() {
+        let mut coffre = Coffre {
+            _id: 2,
+            _id_zone: 1,
+            ouvert: false,
+            _description: "Un coffre piégé".to_string(),
+            inventaire: Inventaire { taille: 1, objets: vec![] },
+            visible: true,
+            difficulte: 0,
+            outil: None,
+            serrure: 70,
+            cle: 15,
+            piege: 20,
+        };
+        assert!((coffre.chance_crochetage(50) - 0.3).abs() < 1e-9);
+        assert_eq!(coffre.chance_crochetage(0), 0.05);
+        assert_eq!(coffre.chance_crochetage(100), 0.8);
+
+        let mut joueur = joueur_test(vec![], 5);
+        joueur.parties_du_corps = vec![crate::personnage::PartieDuCorps::new("Bras droit".to_string(), 75)];
+        assert!(matches!(coffre.declencher_piege(&mut joueur), Some(ResultatBlessure::RienGrave | ResultatBlessure::Blesse)));
+        assert_eq!(joueur.parties_du_corps[0].vie_actuelle(), 55);
+        assert_eq!(coffre.piege, 0);
+        assert!(coffre.declencher_piege(&mut joueur).is_none());
+    }
+}
@@ -2,6 +2,8 @@ use rand::Rng;
 use crate::personnage::Personnage;
 use crate::objet::{OBJETS_DISPONIBLES, TypeObjet};
 use crate::affichage;
+use crate::progression::COURBE_PROGRESSION;
+use crate::quete::{self, Evenement};
 
 /// Résultat d'un combat entre deux personnages.
 pub struct CombatResultat {
@@ -16,8 +18,11 @@ pub struct CombatResultat {
 /// Simule un combat entre deux personnages (joueur et mob).
 ///
 /// Le combat se déroule en tours jusqu'à ce qu'un des personnages ne puisse plus se battre.
-/// À chaque tour, un attaquant est choisi aléatoirement, qui utilise une arme (ou ses mains) pour attaquer une partie du corps de l'adversaire.
-/// Les protections de l'adversaire réduisent les dégâts.
+/// Le premier attaquant est tiré au sort selon l'agilité des deux personnages, puis ils attaquent
+/// chacun leur tour avec une arme (ou leurs mains) une partie du corps de l'adversaire.
+/// Le défenseur peut esquiver selon son agilité ; ses protections réduisent les dégâts.
+/// Chaque coup porté use l'arme et chaque coup encaissé use la protection ; un objet
+/// cassé n'a plus d'effet.
 ///
 /// # Arguments
 /// * `p1` - Le personnage joueur.
@@ -29,7 +34,10 @@ pub struct CombatResultat {
 /// Retourne un `CombatResultat` contenant le vainqueur (s'il y en a un) et l'état final des deux personnages.
 pub fn combattre(mut p1: Personnage, mut p2: Personnage, zone: &crate::zone::Zone, tous_les_pnjs: &[crate::personnage::PNJ]) -> CombatResultat {
     let mut rng = rand::rng();
-    let mut attaquant = if rng.random_bool(0.5) { 0 } else { 1 };
+    // Le plus agile a le plus de chances d'attaquer en premier
+    let agilite_totale = p1.attributs.agilite as f64 + p2.attributs.agilite as f64;
+    let initiative = if agilite_totale > 0.0 { p1.attributs.agilite as f64 / agilite_totale } else { 0.5 };
+    let mut attaquant = if rng.random_bool(initiative) { 0 } else { 1 };
     let mut tour = 0;
     
     //tant que les joueurs peuvent se battre on continue le combat
@@ -40,13 +48,16 @@ pub fn combattre(mut p1: Personnage, mut p2: Personnage, zone: &crate::zone::Zon
             (&mut p2, &mut p1)
         };
 
+        // Arme non cassée portée à un bras, avec l'index de ce bras
         let arme = att.parties_du_corps.iter()
-            .filter(|p| p.nom().to_lowercase().contains("bras"))
-            .flat_map(|bras| bras.equipement().objets.iter())
-            .find_map(|obj_inv| {
+            .enumerate()
+            .filter(|(_, p)| p.nom().to_lowercase().contains("bras"))
+            .flat_map(|(index_bras, bras)| bras.equipement().objets.iter().map(move |obj_inv| (index_bras, obj_inv)))
+            .filter(|(_, obj_inv)| !obj_inv.est_casse())
+            .find_map(|(index_bras, obj_inv)| {
                 let objets = OBJETS_DISPONIBLES.read().unwrap();
                 objets.get(&obj_inv.objet_id).and_then(|o| match &o.objet_type {
-                    TypeObjet::Arme { .. } => Some(o.clone()),
+                    TypeObjet::Arme { .. } => Some((index_bras, obj_inv.clone(), o.clone())),
                     _ => None,
                 })
             });
@@ -60,9 +71,9 @@ pub fn combattre(mut p1: Personnage, mut p2: Personnage, zone: &crate::zone::Zon
         let nom_partie = def.parties_du_corps[index_cible].nom().to_string();
 
         // calcul les degats
-        let (degats, proba, nom_arme) = if let Some(arme) = &arme {
+        let (degats, proba, nom_arme) = if let Some((_, exemplaire, arme)) = &arme {
             if let TypeObjet::Arme { degats, proba_degats, .. } = arme.objet_type {
-                (degats as i32 + att.force_effective() as i32 / 10, proba_degats, arme.nom.clone())
+                (exemplaire.degats(degats) as i32 + att.force_effective() as i32 / 10, proba_degats, exemplaire.nom_affiche(arme))
             } else {
                 (att.force_effective() as i32 / 10, 1.0, "Mains nues".to_string())
             }
@@ -70,14 +81,26 @@ pub fn combattre(mut p1: Personnage, mut p2: Personnage, zone: &crate::zone::Zon
             (att.force_effective() as i32 / 10, 1.0, "Mains nues".to_string())
         };
         
-        if rng.random_bool(proba as f64) {
-            let protection: i32 = def.parties_du_corps[index_cible].equipement().objets.iter().map(|obj_inv| {
-                let objets = OBJETS_DISPONIBLES.read().unwrap();
-                objets.get(&obj_inv.objet_id).and_then(|o| match &o.objet_type {
-                    TypeObjet::Equipement { protection, .. } => Some(*protection as i32),
-                    _ => None,
-                }).unwrap_or(0)
-            }).sum();
+        let esquive = rng.random_bool(def.chance_esquive());
+        if esquive {
+            println!("Tour {tour} : {} esquive l'attaque de {} !", def.nom, att.nom);
+        } else if rng.random_bool(proba as f64) {
+            let protection: i32 = def.parties_du_corps[index_cible].equipement().objets.iter()
+                .filter(|obj_inv| !obj_inv.est_casse())
+                .map(|obj_inv| {
+                    let objets = OBJETS_DISPONIBLES.read().unwrap();
+                    objets.get(&obj_inv.objet_id).and_then(|o| match &o.objet_type {
+                        TypeObjet::Equipement { protection, .. } => Some(obj_inv.protection(*protection) as i32),
+                        _ => None,
+                    }).unwrap_or(0)
+                }).sum();
+            // L'arme qui touche et la protection qui encaisse s'usent
+            if let Some((index_bras, _, _)) = &arme {
+                annoncer_casse(att, *index_bras);
+            }
+            if protection > 0 {
+                annoncer_casse(def, index_cible);
+            }
             let degats_finals = if protection > 0 {
                 let diviseur = (1.5 * protection as f32).floor().max(1.0);
                 ((degats as f32) / diviseur).floor() as u32
@@ -88,9 +111,11 @@ pub fn combattre(mut p1: Personnage, mut p2: Personnage, zone: &crate::zone::Zon
                 def.gerer_blessure(&nom_partie, degats_finals);
             }
         }
-        println!("Tour {tour} : {} attaque {} avec {} sur {} (dégâts: {})", att.nom, def.nom, nom_arme, nom_partie, degats);
-        let partie_cible = &def.parties_du_corps[index_cible];
-        println!("  -> {} de {} : {}/{} HP, état : {}", nom_partie, def.nom, partie_cible.vie_actuelle(), partie_cible.vie_max(), partie_cible.etat());
+        if !esquive {
+            println!("Tour {tour} : {} attaque {} avec {} sur {} (dégâts: {})", att.nom, def.nom, nom_arme, nom_partie, degats);
+            let partie_cible = &def.parties_du_corps[index_cible];
+            println!("  -> {} de {} : {}/{} HP, état : {}", nom_partie, def.nom, partie_cible.vie_actuelle(), partie_cible.vie_max(), partie_cible.etat());
+        }
 
         attaquant = 1 - attaquant;
         tour += 1;
@@ -113,10 +138,130 @@ pub fn combattre(mut p1: Personnage, mut p2: Personnage, zone: &crate::zone::Zon
     }
 }
 
+/// Fait perdre un point de durabilité à l'objet porté par une partie du corps
+/// et annonce s'il se casse.
+fn annoncer_casse(personnage: &mut Personnage, index_partie: usize) {
+    if let Some(objet_id) = personnage.parties_du_corps[index_partie].abimer_equipement() {
+        let nom = OBJETS_DISPONIBLES.read().unwrap().get(&objet_id).map(|o| o.nom.clone()).unwrap_or_default();
+        println!("💥 {} de {} se casse !", nom, personnage.nom);
+    }
+}
+
+/// Issue de l'affrontement entre le joueur et un mob.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResultatAffrontement {
+    /// Le joueur a tué le mob.
+    Victoire,
+    /// Le joueur a fui, le mob est toujours là.
+    Fuite,
+    /// Le joueur a perdu le combat.
+    Defaite,
+}
+
+/// Cherche le premier projectile de l'inventaire du joueur.
+///
+/// # Retour
+/// L'objet du catalogue correspondant, ou `None` si le joueur n'a rien à lancer.
+fn projectile_disponible(joueur: &Personnage) -> Option<crate::objet::Objet> {
+    let objets = OBJETS_DISPONIBLES.read().unwrap();
+    joueur.inventaire.objets.iter()
+        .filter_map(|obj_inv| objets.get(&obj_inv.objet_id))
+        .find(|o| matches!(o.objet_type, TypeObjet::Projectile { .. }))
+        .cloned()
+}
+
+/// Propose au joueur de lancer un projectile sur le mob avant le combat.
+///
+/// Le projectile est consommé même s'il manque sa cible ; s'il touche, il blesse
+/// une partie du corps du mob tirée au hasard.
+fn lancer_projectile(joueur: &mut Personnage, mob: &mut Personnage, zone: &crate::zone::Zone, tous_les_pnjs: &[crate::personnage::PNJ]) {
+    let Some(projectile) = projectile_disponible(joueur) else {
+        return;
+    };
+    let TypeObjet::Projectile { degats, proba_degats } = projectile.objet_type else {
+        return;
+    };
+    let choix = affichage::faire_choix(
+        &format!("Voulez-vous lancer {} sur {} avant le combat ? (oui/non)", projectile.nom, mob.nom),
+        &vec!["oui".to_string(), "non".to_string()]
+    );
+    if choix != "oui" {
+        return;
+    }
+    joueur.inventaire.retirer_par_id(projectile.id);
+    let mut rng = rand::rng();
+    let parties: Vec<String> = mob.parties_du_corps.iter()
+        .filter(|p| !p.est_morte())
+        .map(|p| p.nom().to_string())
+        .collect();
+    let msg = match parties.get(rng.random_range(0..parties.len().max(1))) {
+        Some(partie) if rng.random_bool(proba_degats as f64) => {
+            mob.gerer_blessure(partie, degats);
+            format!("🎯 {} touche {} à : {} !", projectile.nom, mob.nom, partie)
+        }
+        _ => format!("💨 {} manque sa cible.", projectile.nom),
+    };
+    affichage::notifier(zone, &msg, tous_les_pnjs);
+}
+
+/// Fait affronter un mob hostile au joueur.
+///
+/// Le joueur peut d'abord tenter de fuir selon `Personnage::chance_fuite`, puis
+/// lancer un projectile sur le mob. S'il combat et gagne, il garde ses blessures, ramasse l'argent du mob, gagne de
+/// l'expérience et la victoire est signalée aux quêtes.
+///
+/// # Arguments
+/// * `joueur` - Le personnage du joueur.
+/// * `mob` - Le mob qui attaque.
+/// * `zone` - La zone dans laquelle a lieu l'affrontement.
+/// * `tous_les_pnjs` - Liste des PNJs pour l'affichage des notifications.
+///
+/// # Retour
+/// L'issue de l'affrontement.
+pub fn affronter_mob(joueur: &mut Personnage, mob: &Personnage, zone: &crate::zone::Zone, tous_les_pnjs: &[crate::personnage::PNJ]) -> ResultatAffrontement {
+    let mut rng = rand::rng();
+    let choix_fuite = affichage::faire_choix(
+        &format!("{} vous attaque ! Voulez-vous tenter de fuir ? ({}% de chances) (oui/non)",
+            mob.nom, (joueur.chance_fuite(mob) * 100.0) as u32),
+        &vec!["oui".to_string(), "non".to_string()]
+    );
+    if choix_fuite == "oui" {
+        if rng.random_bool(joueur.chance_fuite(mob)) {
+            affichage::notifier(zone, "🏃 Vous parvenez à fuir !", tous_les_pnjs);
+            return ResultatAffrontement::Fuite;
+        }
+        affichage::notifier(zone, "Vous n'arrivez pas à fuir, le combat commence !", tous_les_pnjs);
+    }
+    let mut mob = mob.clone();
+    lancer_projectile(joueur, &mut mob, zone, tous_les_pnjs);
+    let resultat = combattre(joueur.clone(), mob.clone(), zone, tous_les_pnjs);
+    if !resultat.etat_final_joueur.est_vivant {
+        let msg = "Malheureusement vous venez de perdre la partie s'arrete pour vous ... N'hésitez pas a refaire une partie".to_string();
+        affichage::notifier(zone, &msg, tous_les_pnjs);
+        return ResultatAffrontement::Defaite;
+    }
+    joueur.parties_du_corps = resultat.etat_final_joueur.parties_du_corps;
+    for p in &joueur.parties_du_corps {
+        if !p.est_saine() {
+            let msg = format!("Votre {} est blessé", p.nom());
+            affichage::notifier(zone, &msg, tous_les_pnjs)
+        }
+    }
+    affichage::notifier(zone, "Vous avez gagné le combat !", tous_les_pnjs);
+    joueur.ajouter_argent(mob.argent);
+    let msg = format!("Vous ramassez {} pièces d'or sur le mob !", mob.argent);
+    affichage::notifier(zone, &msg, tous_les_pnjs);
+    joueur.gagner_experience(COURBE_PROGRESSION.experience_combat(mob.force));
+    quete::signaler(joueur, &Evenement::MobTue(mob.id));
+    ResultatAffrontement::Victoire
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::personnage::{Personnage, PartieDuCorps};
+    use crate::personnage::{Attributs, Personnage, PartieDuCorps};
+    use crate::outils_test::{catalogue_test, joueur_test, CAILLOU};
+    use crate::progression::Progression;
 
     #[test]
     fn test_combatresultat_structure() {
@@ -129,6 +274,8 @@ mod tests {
             parties_du_corps: vec![PartieDuCorps::new("Tête".to_string(), 10)],
             argent: 0,
             est_vivant: true,
+            progression: Progression::default(),
+            attributs: Attributs::default(),
         };
         let p2 = p1.clone();
         let res = CombatResultat {
@@ -138,4 +285,13 @@ mod tests {
         };
         assert_eq!(res._vainqueur, Some("A".to_string()));
     }
+
+    #[test]
+    fn test_projectile_disponible() {
+        catalogue_test();
+        let mut joueur = joueur_test(vec![], 2);
+        assert!(projectile_disponible(&joueur).is_none());
+        joueur.inventaire.ajouter_objet(CAILLOU).unwrap();
+        assert_eq!(projectile_disponible(&joueur).map(|o| o.id), Some(CAILLOU));
+    }
 }
@@ -0,0 +1,273 @@
+use serde::{Serialize, Deserialize};
+use rand::prelude::IndexedRandom;
+use crate::personnage::Personnage;
+use crate::quete::QUETES_DISPONIBLES;
+
+/// Arbre de dialogue d'un PNJ, chargé depuis `pnj.json`.
+///
+/// Le dialogue commence au nœud `depart` ; chaque choix du joueur peut appliquer
+/// des effets puis mener à un autre nœud, ou terminer la conversation.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArbreDialogue {
+    /// Identifiant du nœud par lequel commence la conversation.
+    pub depart: String,
+    /// Liste des nœuds de l'arbre.
+    pub noeuds: Vec<NoeudDialogue>,
+}
+
+/// Réplique d'un PNJ suivie des réponses proposées au joueur.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NoeudDialogue {
+    /// Identifiant du nœud, référencé par `ChoixDialogue::suivant`.
+    pub id: String,
+    /// Texte prononcé par le PNJ.
+    pub texte: String,
+    /// Réponses possibles du joueur.
+    #[serde(default)]
+    pub choix: Vec<ChoixDialogue>,
+}
+
+/// Réponse que le joueur peut choisir dans un nœud de dialogue.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChoixDialogue {
+    /// Texte de la réponse affichée au joueur.
+    pub texte: String,
+    /// Conditions à remplir pour que la réponse soit proposée.
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    /// Effets appliqués quand le joueur choisit cette réponse.
+    #[serde(default)]
+    pub effets: Vec<Effet>,
+    /// Nœud suivant, ou `None` pour terminer la conversation.
+    #[serde(default)]
+    pub suivant: Option<String>,
+}
+
+/// Condition d'affichage d'une réponse de dialogue.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum Condition {
+    /// Le joueur possède au moins `montant` pièces.
+    ArgentMin { montant: u32 },
+    /// Le joueur possède au moins `nombre` exemplaires de l'objet.
+    PossedeObjet { objet_id: u8, nombre: u8 },
+    /// La réputation du joueur auprès du PNJ est au moins `valeur`.
+    ReputationMin { valeur: i32 },
+    /// Le drapeau d'histoire est posé.
+    Drapeau { nom: String },
+    /// Le drapeau d'histoire n'est pas posé.
+    SansDrapeau { nom: String },
+    /// La quête n'a été ni acceptée ni rendue.
+    QueteDisponible { quete_id: u32 },
+    /// La quête est en cours, ses objectifs ne sont pas forcément remplis.
+    QueteEnCours { quete_id: u32 },
+    /// La quête est en cours et tous ses objectifs sont remplis.
+    QueteAccomplie { quete_id: u32 },
+    /// La quête a été rendue.
+    QueteTerminee { quete_id: u32 },
+}
+
+/// Effet déclenché par une réponse de dialogue.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum Effet {
+    /// Le PNJ donne un objet au joueur.
+    DonnerObjet { objet_id: u8, nombre: u8 },
+    /// Le PNJ prend un objet au joueur.
+    PrendreObjet { objet_id: u8, nombre: u8 },
+    /// Le PNJ donne de l'argent au joueur.
+    DonnerArgent { montant: u32 },
+    /// Le PNJ prend de l'argent au joueur.
+    PrendreArgent { montant: u32 },
+    /// Ouvre l'accès à une zone sans avoir à l'acheter.
+    OuvrirZone { zone_id: u8 },
+    /// Change le multiplicateur de prix du PNJ.
+    ModifierPrix { multiplicateur: f32 },
+    /// Modifie la réputation du joueur auprès du PNJ.
+    ModifierReputation { variation: i32 },
+    /// Pose un drapeau d'histoire dans la progression du joueur.
+    PoserDrapeau { nom: String },
+    /// Confie une quête au joueur.
+    DonnerQuete { quete_id: u32 },
+    /// Rend une quête accomplie et donne ses récompenses.
+    RendreQuete { quete_id: u32 },
+    /// Ouvre la boutique du PNJ.
+    Commercer,
+    /// Déclenche un combat contre le PNJ.
+    Combattre,
+}
+
+impl Condition {
+    /// Vérifie si la condition est remplie pour ce joueur.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur.
+    /// * `reputation` - La réputation du joueur auprès du PNJ qui parle.
+    pub fn est_remplie(&self, joueur: &Personnage, reputation: i32) -> bool {
+        match self {
+            Condition::ArgentMin { montant } => joueur.argent >= *montant,
+            Condition::PossedeObjet { objet_id, nombre } => {
+                let possede: u32 = joueur.inventaire.objets.iter()
+                    .filter(|o| o.objet_id == *objet_id)
+                    .map(|o| o.nombre as u32)
+                    .sum();
+                possede >= *nombre as u32
+            }
+            Condition::ReputationMin { valeur } => reputation >= *valeur,
+            Condition::Drapeau { nom } => joueur.progression.a_drapeau(nom),
+            Condition::SansDrapeau { nom } => !joueur.progression.a_drapeau(nom),
+            Condition::QueteDisponible { quete_id } => {
+                joueur.progression.suivi_quete(*quete_id).is_none()
+                    && !joueur.progression.quete_terminee(*quete_id)
+            }
+            Condition::QueteEnCours { quete_id } => joueur.progression.suivi_quete(*quete_id).is_some(),
+            Condition::QueteAccomplie { quete_id } => QUETES_DISPONIBLES.read().unwrap()
+                .get(quete_id)
+                .is_some_and(|q| q.est_accomplie(joueur)),
+            Condition::QueteTerminee { quete_id } => joueur.progression.quete_terminee(*quete_id),
+        }
+    }
+}
+
+impl Effet {
+    /// Vérifie que le joueur peut donner ce que l'effet lui demande.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur.
+    ///
+    /// # Retour
+    /// `Err` avec un message si le joueur n'a pas l'objet en quantité suffisante
+    /// ou pas assez d'argent.
+    pub fn verifier(&self, joueur: &Personnage) -> Result<(), String> {
+        match self {
+            Effet::PrendreObjet { objet_id, nombre }
+                if !Condition::PossedeObjet { objet_id: *objet_id, nombre: *nombre }.est_remplie(joueur, 0) =>
+            {
+                Err("Vous n'avez pas ce qu'on vous demande !".to_string())
+            }
+            Effet::PrendreArgent { montant } if joueur.argent < *montant => {
+                Err("Vous n'avez pas assez d'argent !".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl ChoixDialogue {
+    /// Crée une réponse sans condition.
+    fn simple(texte: &str, effets: Vec<Effet>, suivant: Option<&str>) -> Self {
+        ChoixDialogue {
+            texte: texte.to_string(),
+            conditions: vec![],
+            effets,
+            suivant: suivant.map(|s| s.to_string()),
+        }
+    }
+
+    /// Vérifie si toutes les conditions de la réponse sont remplies.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur.
+    /// * `reputation` - La réputation du joueur auprès du PNJ qui parle.
+    pub fn est_disponible(&self, joueur: &Personnage, reputation: i32) -> bool {
+        self.conditions.iter().all(|c| c.est_remplie(joueur, reputation))
+    }
+}
+
+impl ArbreDialogue {
+    /// Construit l'arbre utilisé par les PNJs qui n'en définissent pas,
+    /// à partir de leur liste de répliques.
+    ///
+    /// Le joueur peut discuter (une réplique au hasard), commercer, attaquer ou partir.
+    ///
+    /// # Arguments
+    /// * `dialogues` - Les répliques du PNJ.
+    pub fn par_defaut(dialogues: &[String]) -> Self {
+        let mut rng = rand::rng();
+        let accueil = dialogues.first().cloned().unwrap_or_else(|| "Bonjour.".to_string());
+        let discussion = dialogues.get(1..).and_then(|autres| autres.choose(&mut rng)).cloned()
+            .unwrap_or_else(|| accueil.clone());
+        let choix_accueil = vec![
+            ChoixDialogue::simple("Discuter", vec![], Some("discussion")),
+            ChoixDialogue::simple("Voir vos marchandises", vec![Effet::Commercer], Some("accueil")),
+            ChoixDialogue::simple("Attaquer", vec![Effet::Combattre], None),
+            ChoixDialogue::simple("Partir", vec![], None),
+        ];
+        ArbreDialogue {
+            depart: "accueil".to_string(),
+            noeuds: vec![
+                NoeudDialogue { id: "accueil".to_string(), texte: accueil, choix: choix_accueil },
+                NoeudDialogue {
+                    id: "discussion".to_string(),
+                    texte: discussion,
+                    choix: vec![ChoixDialogue::simple("Revenir", vec![], Some("accueil"))],
+                },
+            ],
+        }
+    }
+
+    /// Recherche un nœud par son identifiant.
+    ///
+    /// # Arguments
+    /// * `id` - Identifiant du nœud.
+    pub fn noeud(&self, id: &str) -> Option<&NoeudDialogue> {
+        self.noeuds.iter().find(|n| n.id == id)
+    }
+
+    /// Vérifie que le nœud de départ et tous les nœuds suivants référencés existent.
+    ///
+    /// # Retour
+    /// `Err` avec un message décrivant la première référence invalide.
+    pub fn verifier(&self) -> Result<(), String> {
+        if self.noeud(&self.depart).is_none() {
+            return Err(format!("Nœud de départ inconnu : {}", self.depart));
+        }
+        for noeud in &self.noeuds {
+            for choix in &noeud.choix {
+                if let Some(suivant) = &choix.suivant {
+                    if self.noeud(suivant).is_none() {
+                        return Err(format!("Le nœud {} mène au nœud inconnu {}", noeud.id, suivant));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arbre_par_defaut_valide() {
+        let arbre = ArbreDialogue::par_defaut(&["Bonjour !".to_string(), "Il fait beau.".to_string()]);
+        assert!(arbre.verifier().is_ok());
+        assert_eq!(arbre.noeud("accueil").unwrap().texte, "Bonjour !");
+        assert_eq!(arbre.noeud("discussion").unwrap().texte, "Il fait beau.");
+        assert!(ArbreDialogue::par_defaut(&[]).verifier().is_ok());
+    }
+
+    #[test]
+    fn test_conditions_et_json() {
+        let json = r#"{
+            "depart": "debut",
+            "noeuds": [
+                { "id": "debut", "texte": "Tu as de quoi payer ?", "choix": [
+                    { "texte": "Oui", "conditions": [{ "ArgentMin": { "montant": 10 } }],
+                      "effets": [{ "PrendreArgent": { "montant": 10 } }, { "PoserDrapeau": { "nom": "a_paye" } }] },
+                    { "texte": "Au revoir", "suivant": "fin" }
+                ]}
+            ]
+        }"#;
+        let arbre: ArbreDialogue = serde_json::from_str(json).unwrap();
+        assert!(arbre.verifier().is_err());
+
+        let mut joueur = crate::outils_test::joueur_test(vec![], 5);
+        joueur.argent = 5;
+        let choix = &arbre.noeud("debut").unwrap().choix[0];
+        assert!(!choix.est_disponible(&joueur, 0));
+        joueur.argent = 10;
+        assert!(choix.est_disponible(&joueur, 0));
+        assert!(Condition::SansDrapeau { nom: "a_paye".to_string() }.est_remplie(&joueur, 0));
+        assert!(!Condition::ReputationMin { valeur: 10 }.est_remplie(&joueur, 0));
+    }
+}
@@ -0,0 +1,164 @@
+use std::error::Error;
+use std::fs;
+use rand::Rng;
+use rand::prelude::{IndexedRandom, SliceRandom};
+use serde::{Serialize, Deserialize};
+use crate::affichage;
+use crate::combat::{self, ResultatAffrontement};
+use crate::personnage::{Mob, Personnage, PNJ};
+use crate::zone::Zone;
+
+/// Événement pouvant survenir à l'arrivée dans une zone, chargé depuis `evenement.json`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EvenementAleatoire {
+    /// Texte affiché quand l'événement se produit.
+    pub description: String,
+    /// Zones où l'événement peut se produire ; toutes si la liste est vide.
+    #[serde(default)]
+    pub zones: Vec<u8>,
+    /// Probabilité que l'événement se produise à chaque arrivée (entre 0.0 et 1.0).
+    pub chance: f64,
+    /// Effet de l'événement.
+    pub effet: EffetEvenement,
+}
+
+/// Effet d'un événement aléatoire.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum EffetEvenement {
+    /// Le joueur trouve une somme comprise entre `min` et `max`.
+    TrouverArgent { min: u32, max: u32 },
+    /// Un voleur dérobe un objet au hasard ; le joueur peut le poursuivre.
+    PerdreObjet,
+    /// Un mob tend une embuscade ; au hasard si `mob_id` n'est pas précisé.
+    Embuscade {
+        #[serde(default)]
+        mob_id: Option<u32>,
+    },
+    /// Le joueur croise un PNJ de passage et peut lui parler.
+    RencontrePnj { pnj_id: u32 },
+    /// La météo de la zone change pour l'une des météos proposées.
+    ChangerMeteo { meteos: Vec<String> },
+}
+
+impl EvenementAleatoire {
+    /// Charge la liste des événements depuis un fichier JSON.
+    ///
+    /// # Arguments
+    /// * `fichier` - Chemin du fichier JSON.
+    pub fn charger(fichier: &str) -> Result<Vec<EvenementAleatoire>, Box<dyn Error>> {
+        let contenu = fs::read_to_string(fichier)?;
+        Ok(serde_json::from_str(&contenu)?)
+    }
+
+    /// Indique si l'événement peut se produire dans la zone.
+    pub fn concerne(&self, zone_id: u8) -> bool {
+        self.zones.is_empty() || self.zones.contains(&zone_id)
+    }
+}
+
+/// Tire au plus un événement pour la zone d'arrivée.
+///
+/// Les événements de la zone sont examinés dans un ordre aléatoire et le
+/// premier dont le tirage réussit est retenu.
+///
+/// # Arguments
+/// * `evenements` - Tous les événements du jeu.
+/// * `zone_id` - Identifiant de la zone d'arrivée.
+pub fn tirer(evenements: &[EvenementAleatoire], zone_id: u8) -> Option<&EvenementAleatoire> {
+    let mut rng = rand::rng();
+    let mut candidats: Vec<&EvenementAleatoire> = evenements.iter().filter(|e| e.concerne(zone_id)).collect();
+    candidats.shuffle(&mut rng);
+    candidats.into_iter().find(|e| rng.random_bool(e.chance.clamp(0.0, 1.0)))
+}
+
+/// Applique un événement au joueur, en lui laissant le choix quand c'est pertinent.
+///
+/// # Arguments
+/// * `evenement` - L'événement à appliquer.
+/// * `joueur` - Le personnage du joueur.
+/// * `zones` - Toutes les zones du jeu.
+/// * `current_zone_index` - Index de la zone où se trouve le joueur.
+/// * `pnjs` - Tous les PNJs du jeu.
+/// * `mobs` - Les mobs du jeu, parmi lesquels choisir l'auteur d'une embuscade.
+///
+/// # Retour
+/// `false` si le joueur est mort pendant l'événement.
+pub fn declencher(evenement: &EvenementAleatoire, joueur: &mut Personnage, zones: &mut [Zone], current_zone_index: usize, pnjs: &mut [PNJ], mobs: &[Mob]) -> bool {
+    let mut rng = rand::rng();
+    affichage::notifier(&zones[current_zone_index], &format!("🎲 {}", evenement.description), pnjs);
+    match &evenement.effet {
+        EffetEvenement::TrouverArgent { min, max } => {
+            let montant = rng.random_range(*min..=(*max).max(*min));
+            joueur.ajouter_argent(montant);
+            affichage::notifier(&zones[current_zone_index], &format!("💰 Vous empochez {} pièces.", montant), pnjs);
+        }
+        EffetEvenement::PerdreObjet => {
+            let Some(objet_id) = joueur.inventaire.objets.choose(&mut rng).map(|o| o.objet_id) else {
+                affichage::notifier(&zones[current_zone_index], "Le voleur repart bredouille, vos poches sont vides.", pnjs);
+                return true;
+            };
+            let choix = affichage::faire_choix(
+                "Un voleur s'enfuit avec l'un de vos objets ! Le poursuivre ? (oui/non)",
+                &vec!["oui".to_string(), "non".to_string()]
+            );
+            let chance_rattraper = (joueur.attributs.agilite as f64 / 100.0).clamp(0.1, 0.9);
+            if choix == "oui" && rng.random_bool(chance_rattraper) {
+                affichage::notifier(&zones[current_zone_index], "🏃 Vous rattrapez le voleur et récupérez votre bien !", pnjs);
+            } else {
+                joueur.inventaire.retirer_par_id(objet_id);
+                affichage::notifier(&zones[current_zone_index], "❌ Le voleur disparaît avec votre objet.", pnjs);
+            }
+        }
+        EffetEvenement::Embuscade { mob_id } => {
+            let mob = match mob_id {
+                Some(id) => mobs.iter().find(|m| m.personnage.id == *id),
+                None => mobs.choose(&mut rng),
+            };
+            if let Some(mob) = mob {
+                let resultat = combat::affronter_mob(joueur, &mob.personnage, &zones[current_zone_index], pnjs);
+                return resultat != ResultatAffrontement::Defaite;
+            }
+        }
+        EffetEvenement::RencontrePnj { pnj_id } => {
+            let zone_id = zones[current_zone_index].id as u32;
+            let Some(index) = pnjs.iter().position(|p| p.personnage.id == *pnj_id && p.personnage.est_vivant && p.zone_id != zone_id) else {
+                return true;
+            };
+            let choix = affichage::faire_choix(
+                &format!("Vous croisez {} sur la route. Lui parler ? (oui/non)", pnjs[index].personnage.nom),
+                &vec!["oui".to_string(), "non".to_string()]
+            );
+            if choix == "oui" {
+                let resultat = pnjs[index].interagir(joueur, zones, current_zone_index);
+                PNJ::reagir_violence(pnjs, index, &resultat);
+            }
+        }
+        EffetEvenement::ChangerMeteo { meteos } => {
+            if let Some(meteo) = meteos.choose(&mut rng) {
+                zones[current_zone_index].meteo = Some(meteo.clone());
+                affichage::notifier(&zones[current_zone_index], &format!("🌦️ Le temps change : {}.", meteo), pnjs);
+            }
+        }
+    }
+    joueur.est_vivant
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charger_et_tirer() {
+        let evenements = EvenementAleatoire::charger("src/json/evenement.json").unwrap();
+        assert!(!evenements.is_empty());
+
+        let json = r#"[
+            { "description": "Certain", "zones": [1], "chance": 1.0, "effet": { "TrouverArgent": { "min": 1, "max": 5 } } },
+            { "description": "Jamais", "chance": 0.0, "effet": { "Embuscade": {} } }
+        ]"#;
+        let evenements: Vec<EvenementAleatoire> = serde_json::from_str(json).unwrap();
+        assert_eq!(evenements[1].effet, EffetEvenement::Embuscade { mob_id: None });
+        assert_eq!(tirer(&evenements, 1).unwrap().description, "Certain");
+        assert!(tirer(&evenements, 2).is_none());
+    }
+}
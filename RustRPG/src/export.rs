@@ -0,0 +1,257 @@
+use std::fs;
+use std::io;
+use serde_json::{json, Value};
+use crate::generation::direction_opposee;
+use crate::zone::{Connexion, Exigence, Zone};
+
+/// Défaut repéré dans le graphe des zones.
+#[derive(Debug, PartialEq)]
+pub enum Anomalie {
+    /// La zone d'arrivée n'a aucune connexion qui ramène à la zone de départ,
+    /// pas même un passage déclaré à sens unique.
+    Asymetrique { depart: u8, arrivee: u8, direction: String },
+    /// La zone d'arrivée ramène à la zone de départ, mais pas par la direction opposée :
+    /// la carte ne peut pas placer les deux zones.
+    RetourIncoherent { depart: u8, arrivee: u8, direction: String, retour: String },
+    /// La connexion mène à une zone qui n'existe pas.
+    Orpheline { depart: u8, id_dest: String, direction: String },
+}
+
+impl Anomalie {
+    /// Décrit l'anomalie en une ligne lisible.
+    pub fn description(&self) -> String {
+        match self {
+            Anomalie::Asymetrique { depart, arrivee, direction } =>
+                format!("Zone {} -> {} ({}) : aucune connexion retour", depart, arrivee, direction),
+            Anomalie::RetourIncoherent { depart, arrivee, direction, retour } =>
+                format!("Zone {} -> {} ({}) : le retour part vers {} au lieu de {}", depart, arrivee, direction, retour,
+                    direction_opposee(direction).unwrap_or("la direction opposée")),
+            Anomalie::Orpheline { depart, id_dest, direction } =>
+                format!("Zone {} ({}) : la zone '{}' n'existe pas", depart, direction, id_dest),
+        }
+    }
+}
+
+/// Détermine l'anomalie éventuelle d'une connexion.
+///
+/// Une connexion marquée `Exigence::SensUnique` est le retour volontairement
+/// impraticable d'un passage à sens unique : seule son arrivée est vérifiée.
+fn anomalie_connexion(zones: &[Zone], zone: &Zone, conn: &Connexion) -> Option<Anomalie> {
+    let Some(dest) = zones.iter().find(|z| z.id.to_string() == conn.id_dest) else {
+        return Some(Anomalie::Orpheline { depart: zone.id, id_dest: conn.id_dest.clone(), direction: conn.direction.clone() });
+    };
+    if conn.exigences.contains(&Exigence::SensUnique) {
+        return None;
+    }
+    let retours: Vec<&Connexion> = dest.connection.iter().filter(|c| c.id_dest == zone.id.to_string()).collect();
+    let Some(premier_retour) = retours.first() else {
+        return Some(Anomalie::Asymetrique { depart: zone.id, arrivee: dest.id, direction: conn.direction.clone() });
+    };
+    match direction_opposee(&conn.direction) {
+        Some(opposee) if !retours.iter().any(|c| c.direction == opposee) => Some(Anomalie::RetourIncoherent {
+            depart: zone.id,
+            arrivee: dest.id,
+            direction: conn.direction.clone(),
+            retour: premier_retour.direction.clone(),
+        }),
+        _ => None,
+    }
+}
+
+/// Liste les connexions asymétriques, au retour incohérent ou orphelines du graphe des zones.
+///
+/// # Arguments
+/// * `zones` - Les zones à analyser.
+pub fn anomalies(zones: &[Zone]) -> Vec<Anomalie> {
+    zones_triees(zones).into_iter()
+        .flat_map(|zone| zone.connection.iter().filter_map(move |conn| anomalie_connexion(zones, zone, conn)))
+        .collect()
+}
+
+/// Trie les zones par identifiant pour produire des exports stables.
+fn zones_triees(zones: &[Zone]) -> Vec<&Zone> {
+    let mut triees: Vec<&Zone> = zones.iter().collect();
+    triees.sort_by_key(|z| z.id);
+    triees
+}
+
+/// Échappe une chaîne pour l'utiliser entre guillemets dans un fichier DOT.
+fn echapper_dot(texte: &str) -> String {
+    texte.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Construit le graphe des zones au format Graphviz DOT.
+///
+/// Chaque zone est étiquetée avec son nom, son prix et la présence de mobs ; chaque
+/// connexion avec sa direction. Les connexions sans retour ou dont le retour ne part pas
+/// dans la direction opposée sont dessinées en orange, et celles qui mènent à une zone
+/// inexistante en rouge, vers un nœud dédié.
+/// Les passages à sens unique sont en pointillés.
+///
+/// # Arguments
+/// * `zones` - Les zones à exporter.
+pub fn exporter_dot(zones: &[Zone]) -> String {
+    let zones_triees = zones_triees(zones);
+    let mut dot = String::from("digraph monde {\n    node [shape=box];\n");
+    for zone in &zones_triees {
+        let style = if zone.mob_present { ", style=filled, fillcolor=\"#f4cccc\"" } else { "" };
+        dot.push_str(&format!(
+            "    z{} [label=\"{} - {}\\nprix : {}\\nmobs : {}\"{}];\n",
+            zone.id, zone.id, echapper_dot(&zone.nom), zone.prix, if zone.mob_present { "oui" } else { "non" }, style
+        ));
+    }
+    for zone in zones_triees {
+        for conn in &zone.connection {
+            let mut attributs = vec![format!("label=\"{}\"", echapper_dot(&conn.direction))];
+            if conn.exigences.contains(&Exigence::SensUnique) {
+                attributs.push("style=dotted".to_string());
+            }
+            let cible = match anomalie_connexion(zones, zone, conn) {
+                Some(Anomalie::Orpheline { .. }) => {
+                    let cible = format!("\"manquante_{}\"", echapper_dot(&conn.id_dest));
+                    dot.push_str(&format!(
+                        "    {} [label=\"Zone '{}' introuvable\", color=red, fontcolor=red];\n",
+                        cible, echapper_dot(&conn.id_dest)
+                    ));
+                    attributs.push("color=red".to_string());
+                    cible
+                }
+                Some(Anomalie::Asymetrique { arrivee, .. } | Anomalie::RetourIncoherent { arrivee, .. }) => {
+                    attributs.push("color=orange".to_string());
+                    format!("z{}", arrivee)
+                }
+                None => format!("z{}", conn.id_dest),
+            };
+            dot.push_str(&format!("    z{} -> {} [{}];\n", zone.id, cible, attributs.join(", ")));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Construit la liste d'adjacence des zones au format JSON, avec les anomalies relevées.
+///
+/// # Arguments
+/// * `zones` - Les zones à exporter.
+pub fn exporter_json(zones: &[Zone]) -> Value {
+    let zones_json: Vec<Value> = zones_triees(zones).into_iter().map(|zone| {
+        let connexions: Vec<Value> = zone.connection.iter().map(|conn| {
+            let anomalie = anomalie_connexion(zones, zone, conn);
+            json!({
+                "direction": conn.direction,
+                "vers": conn.id_dest,
+                "exigences": conn.exigences.len(),
+                "sens_unique": conn.exigences.contains(&Exigence::SensUnique),
+                "asymetrique": matches!(anomalie, Some(Anomalie::Asymetrique { .. })),
+                "retour_incoherent": matches!(anomalie, Some(Anomalie::RetourIncoherent { .. })),
+                "orpheline": matches!(anomalie, Some(Anomalie::Orpheline { .. })),
+            })
+        }).collect();
+        json!({
+            "id": zone.id,
+            "nom": zone.nom,
+            "prix": zone.prix,
+            "ouvert": zone.ouvert,
+            "mob_present": zone.mob_present,
+            "connexions": connexions,
+        })
+    }).collect();
+    let anomalies: Vec<String> = anomalies(zones).iter().map(Anomalie::description).collect();
+    json!({ "zones": zones_json, "anomalies": anomalies })
+}
+
+/// Écrit le graphe des zones dans `<prefixe>.dot` et `<prefixe>.json`.
+///
+/// # Arguments
+/// * `zones` - Les zones à exporter.
+/// * `prefixe` - Chemin des fichiers, sans extension.
+pub fn exporter(zones: &[Zone], prefixe: &str) -> io::Result<()> {
+    fs::write(format!("{}.dot", prefixe), exporter_dot(zones))?;
+    let json = serde_json::to_string_pretty(&exporter_json(zones))?;
+    fs::write(format!("{}.json", prefixe), json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outils_test::zone_test;
+
+    /// Relie une zone à une autre dans une direction.
+    fn connexion(direction: &str, id_dest: &str) -> Connexion {
+        Connexion { direction: direction.to_string(), id_dest: id_dest.to_string(), exigences: vec![] }
+    }
+
+    /// Construit un petit monde : 1 et 2 reliées dans les deux sens, un passage
+    /// sans retour de 2 vers 3, une sortie de 3 vers une zone inexistante et un
+    /// passage à sens unique déclaré de 1 vers 4 et un chemin de 1 vers 5 dont le
+    /// retour part aussi vers le sud.
+    fn petit_monde() -> Vec<Zone> {
+        let sens_unique = Connexion { exigences: vec![Exigence::SensUnique], ..connexion("est", "1") };
+        vec![
+            Zone { nom: "Gouffre".to_string(), connection: vec![sens_unique], ..zone_test(4) },
+            Zone { nom: "Pic".to_string(), connection: vec![connexion("ouest", "42")], ..zone_test(3) },
+            Zone { nom: "Lac".to_string(), connection: vec![connexion("sud", "1")], ..zone_test(5) },
+            Zone {
+                nom: "Forêt".to_string(),
+                connection: vec![connexion("nord", "2"), connexion("ouest", "4"), connexion("sud", "5")],
+                ..zone_test(1)
+            },
+            Zone {
+                nom: "Grotte \"noire\"".to_string(),
+                prix: 50,
+                mob_present: true,
+                connection: vec![connexion("sud", "1"), connexion("est", "3")],
+                ..zone_test(2)
+            },
+        ]
+    }
+
+    /// Vérifie que les anomalies sont relevées et que les exports les signalent.
+    #[test]
+    fn test_export_du_graphe() {
+        let zones = petit_monde();
+        assert_eq!(anomalies(&zones), vec![
+            Anomalie::RetourIncoherent { depart: 1, arrivee: 5, direction: "sud".to_string(), retour: "sud".to_string() },
+            Anomalie::Asymetrique { depart: 2, arrivee: 3, direction: "est".to_string() },
+            Anomalie::Orpheline { depart: 3, id_dest: "42".to_string(), direction: "ouest".to_string() },
+            Anomalie::RetourIncoherent { depart: 5, arrivee: 1, direction: "sud".to_string(), retour: "sud".to_string() },
+        ]);
+
+        assert_eq!(exporter_dot(&zones), concat!(
+            "digraph monde {\n",
+            "    node [shape=box];\n",
+            "    z1 [label=\"1 - Forêt\\nprix : 0\\nmobs : non\"];\n",
+            "    z2 [label=\"2 - Grotte \\\"noire\\\"\\nprix : 50\\nmobs : oui\", style=filled, fillcolor=\"#f4cccc\"];\n",
+            "    z3 [label=\"3 - Pic\\nprix : 0\\nmobs : non\"];\n",
+            "    z4 [label=\"4 - Gouffre\\nprix : 0\\nmobs : non\"];\n",
+            "    z5 [label=\"5 - Lac\\nprix : 0\\nmobs : non\"];\n",
+            "    z1 -> z2 [label=\"nord\"];\n",
+            "    z1 -> z4 [label=\"ouest\"];\n",
+            "    z1 -> z5 [label=\"sud\", color=orange];\n",
+            "    z2 -> z1 [label=\"sud\"];\n",
+            "    z2 -> z3 [label=\"est\", color=orange];\n",
+            "    \"manquante_42\" [label=\"Zone '42' introuvable\", color=red, fontcolor=red];\n",
+            "    z3 -> \"manquante_42\" [label=\"ouest\", color=red];\n",
+            "    z4 -> z1 [label=\"est\", style=dotted];\n",
+            "    z5 -> z1 [label=\"sud\", color=orange];\n",
+            "}\n",
+        ));
+
+        let json = exporter_json(&zones);
+        assert_eq!(json["anomalies"], json!([
+            "Zone 1 -> 5 (sud) : le retour part vers sud au lieu de nord",
+            "Zone 2 -> 3 (est) : aucune connexion retour",
+            "Zone 3 (ouest) : la zone '42' n'existe pas",
+            "Zone 5 -> 1 (sud) : le retour part vers sud au lieu de nord",
+        ]));
+        assert_eq!(json["zones"][1]["connexions"], json!([
+            { "direction": "sud", "vers": "1", "exigences": 0, "sens_unique": false, "asymetrique": false, "retour_incoherent": false, "orpheline": false },
+            { "direction": "est", "vers": "3", "exigences": 0, "sens_unique": false, "asymetrique": true, "retour_incoherent": false, "orpheline": false },
+        ]));
+        assert_eq!(json["zones"][2]["connexions"][0]["orpheline"], json!(true));
+        assert_eq!(json["zones"][1]["mob_present"], json!(true));
+        assert_eq!(json["zones"][3]["connexions"][0]["sens_unique"], json!(true));
+        assert_eq!(json["zones"][3]["connexions"][0]["asymetrique"], json!(false));
+        assert_eq!(json["zones"][4]["connexions"][0]["retour_incoherent"], json!(true));
+    }
+}
@@ -0,0 +1,320 @@
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fs;
+use rand::prelude::{IndexedRandom, SliceRandom};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use crate::coffre::{self, Coffre};
+use crate::inventaire::Inventaire;
+use crate::zone::{Connexion, Zone};
+
+/// Directions de connexion, avec leur décalage sur la grille et la direction opposée.
+const DIRECTIONS: [(&str, (i32, i32), &str); 4] = [
+    ("nord", (0, -1), "sud"),
+    ("sud", (0, 1), "nord"),
+    ("est", (1, 0), "ouest"),
+    ("ouest", (-1, 0), "est"),
+];
+/// Nombre maximal de coffres, leurs identifiants tenant sur un `u8`.
+const COFFRES_MAX: usize = u8::MAX as usize;
+
+/// Renvoie la direction opposée d'un point cardinal, celle que doit prendre la connexion retour.
+///
+/// # Retour
+/// `None` si la direction ne correspond à aucun point cardinal.
+pub fn direction_opposee(direction: &str) -> Option<&'static str> {
+    DIRECTIONS.iter().find(|(d, _, _)| *d == direction).map(|(_, _, opposee)| *opposee)
+}
+
+/// Type de lieu servant de modèle au nom et à la description d'une zone.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ModeleLieu {
+    /// Début du nom de la zone (ex. : "Forêt").
+    pub nom: String,
+    /// Première phrase de la description.
+    pub description: String,
+}
+
+/// Modèles de textes du générateur, chargés depuis `generation.json`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Modeles {
+    /// Types de lieux.
+    pub lieux: Vec<ModeleLieu>,
+    /// Compléments ajoutés au nom du lieu (ex. : "des Murmures").
+    pub qualificatifs: Vec<String>,
+    /// Phrases d'ambiance ajoutées à la description.
+    pub ambiances: Vec<String>,
+    /// Descriptions des coffres.
+    pub coffres: Vec<String>,
+}
+
+impl Modeles {
+    /// Charge les modèles depuis un fichier JSON.
+    ///
+    /// # Arguments
+    /// * `fichier` - Chemin du fichier JSON.
+    pub fn charger(fichier: &str) -> Result<Modeles, Box<dyn Error>> {
+        let contenu = fs::read_to_string(fichier)?;
+        Ok(serde_json::from_str(&contenu)?)
+    }
+}
+
+/// Réglages de la génération procédurale du monde.
+#[derive(Debug, Clone)]
+pub struct ParametresGeneration {
+    /// Graine du générateur : une même graine produit le même monde.
+    pub graine: u64,
+    /// Nombre de zones à créer (au moins une).
+    pub nombre_zones: u8,
+    /// Nombre moyen de coffres par zone.
+    pub densite_coffres: f64,
+    /// Probabilité qu'une zone, hors zone de départ, soit peuplée de mobs.
+    pub densite_mobs: f64,
+    /// Probabilité qu'une zone puisse accueillir des PNJs ; la zone de départ en accueille toujours.
+    pub densite_pnjs: f64,
+    /// Prix d'une zone par connexion la séparant de la zone de départ.
+    pub prix_par_distance: u32,
+    /// Probabilité de relier une nouvelle zone à une voisine déjà créée, en plus de sa zone d'origine.
+    pub chance_boucle: f64,
+}
+
+impl Default for ParametresGeneration {
+    fn default() -> Self {
+        ParametresGeneration {
+            graine: 0,
+            nombre_zones: 10,
+            densite_coffres: 2.0,
+            densite_mobs: 0.7,
+            densite_pnjs: 0.4,
+            prix_par_distance: 100,
+            chance_boucle: 0.3,
+        }
+    }
+}
+
+/// Résultat de la génération : les zones et l'emplacement des PNJs.
+#[derive(Debug)]
+pub struct MondeGenere {
+    /// Zones générées ; la zone de départ a l'identifiant 1.
+    pub zones: Vec<Zone>,
+    /// Zone attribuée à chaque PNJ, sous la forme `(pnj_id, zone_id)`.
+    pub placements_pnjs: Vec<(u32, u32)>,
+}
+
+/// Crée une connexion sans exigence.
+fn connexion(direction: &str, id_dest: u8) -> Connexion {
+    Connexion { direction: direction.to_string(), id_dest: id_dest.to_string(), exigences: vec![] }
+}
+
+/// Tire un nom de zone inédit à partir des modèles.
+fn tirer_nom(rng: &mut StdRng, modeles: &Modeles, noms_pris: &[String]) -> (String, usize) {
+    let mut index_lieu = 0;
+    let mut nom = String::new();
+    for _ in 0..20 {
+        index_lieu = rng.random_range(0..modeles.lieux.len());
+        let qualificatif = modeles.qualificatifs.choose(rng).map(String::as_str).unwrap_or("");
+        nom = format!("{} {}", modeles.lieux[index_lieu].nom, qualificatif).trim().to_string();
+        if !noms_pris.contains(&nom) {
+            return (nom, index_lieu);
+        }
+    }
+    (format!("{} {}", nom, noms_pris.len() + 1), index_lieu)
+}
+
+/// Nombre de connexions séparant chaque zone de la zone d'index 0.
+fn distances(zones: &[Zone]) -> Vec<u32> {
+    let mut distances = vec![u32::MAX; zones.len()];
+    let mut a_traiter = VecDeque::from([0usize]);
+    distances[0] = 0;
+    while let Some(index) = a_traiter.pop_front() {
+        for id in zones[index].destinations() {
+            let suivant = id as usize - 1;
+            if distances[suivant] == u32::MAX {
+                distances[suivant] = distances[index] + 1;
+                a_traiter.push_back(suivant);
+            }
+        }
+    }
+    distances
+}
+
+/// Génère un monde : un graphe connexe de zones disposées sur une grille, reliées
+/// dans les deux sens par des directions opposées.
+///
+/// Chaque nouvelle zone est accrochée à une case libre voisine d'une zone existante,
+/// et parfois reliée à ses autres voisines pour former des boucles. Le prix d'une
+/// zone croît avec sa distance à la zone de départ, seule zone ouverte ; les coffres
+/// y sont plus difficiles à trouver et à ouvrir. Les PNJs sont répartis entre la zone
+/// de départ et les zones tirées selon `densite_pnjs`.
+///
+/// # Arguments
+/// * `parametres` - Réglages de la génération.
+/// * `modeles` - Modèles de noms et de descriptions.
+/// * `pnj_ids` - Identifiants des PNJs à placer.
+pub fn generer(parametres: &ParametresGeneration, modeles: &Modeles, pnj_ids: &[u32]) -> MondeGenere {
+    let mut rng = StdRng::seed_from_u64(parametres.graine);
+    let nombre_zones = parametres.nombre_zones.max(1);
+    let mut occupees: HashMap<(i32, i32), u8> = HashMap::new();
+    let mut cases: Vec<(i32, i32)> = Vec::new();
+    let mut zones: Vec<Zone> = Vec::new();
+    let mut noms: Vec<String> = Vec::new();
+
+    for id in 1..=nombre_zones {
+        let case = if id == 1 {
+            zones.push(zone_vide(id));
+            (0, 0)
+        } else {
+            // Cases libres voisines des zones existantes, dans un ordre stable
+            let mut candidates: Vec<((i32, i32), u8, usize)> = Vec::new();
+            for (index_zone, origine) in cases.iter().enumerate() {
+                for (index, (_, (dx, dy), _)) in DIRECTIONS.iter().enumerate() {
+                    let case = (origine.0 + dx, origine.1 + dy);
+                    if !occupees.contains_key(&case) {
+                        candidates.push((case, index_zone as u8 + 1, index));
+                    }
+                }
+            }
+            let &(case, origine, index) = candidates.choose(&mut rng).expect("une grille infinie a toujours une case libre");
+            let (direction, _, opposee) = DIRECTIONS[index];
+            zones[origine as usize - 1].connection.push(connexion(direction, id));
+            let mut connexions = vec![connexion(opposee, origine)];
+            // Boucles vers les autres voisines déjà créées
+            for (direction, (dx, dy), opposee) in DIRECTIONS {
+                let Some(&voisine) = occupees.get(&(case.0 + dx, case.1 + dy)) else { continue };
+                if voisine != origine && rng.random_bool(parametres.chance_boucle.clamp(0.0, 1.0)) {
+                    connexions.push(connexion(direction, voisine));
+                    zones[voisine as usize - 1].connection.push(connexion(opposee, id));
+                }
+            }
+            zones.push(Zone { connection: connexions, ..zone_vide(id) });
+            case
+        };
+        occupees.insert(case, id);
+        cases.push(case);
+
+        let (nom, index_lieu) = tirer_nom(&mut rng, modeles, &noms);
+        let lieu = &modeles.lieux[index_lieu];
+        let ambiance = modeles.ambiances.choose(&mut rng).map(String::as_str).unwrap_or("");
+        let zone = &mut zones[id as usize - 1];
+        zone.description = format!("{} {}", lieu.description, ambiance).trim().to_string();
+        zone.nom = nom.clone();
+        noms.push(nom);
+    }
+
+    let distances = distances(&zones);
+    let mut prochain_coffre = 1usize;
+    for (zone, distance) in zones.iter_mut().zip(distances) {
+        zone.prix = distance * parametres.prix_par_distance;
+        zone.ouvert = zone.prix == 0;
+        zone.mob_present = zone.id != 1 && rng.random_bool(parametres.densite_mobs.clamp(0.0, 1.0));
+
+        let densite = parametres.densite_coffres.max(0.0);
+        let nombre_coffres = densite.floor() as usize + usize::from(rng.random_bool(densite.fract()));
+        for _ in 0..nombre_coffres {
+            if prochain_coffre > COFFRES_MAX {
+                break;
+            }
+            let difficulte_max = (distance * 10).min(90) as u8;
+            let visible = rng.random_bool(0.7);
+            zone.coffres.push(Coffre {
+                _id: prochain_coffre as u8,
+                _id_zone: zone.id,
+                ouvert: rng.random_bool(0.5),
+                _description: modeles.coffres.choose(&mut rng).cloned().unwrap_or_default(),
+                inventaire: Inventaire { taille: rng.random_range(3..=10), objets: vec![] },
+                visible,
+                difficulte: if visible { 0 } else { rng.random_range(0..=difficulte_max) },
+                outil: None,
+                serrure: rng.random_range(0..=difficulte_max),
+                cle: coffre::ID_CLE,
+                piege: if rng.random_bool(0.15) { 10 + distance * 5 } else { 0 },
+            });
+            prochain_coffre += 1;
+        }
+    }
+
+    let mut habitables: Vec<u32> = zones.iter()
+        .filter(|z| z.id == 1 || rng.random_bool(parametres.densite_pnjs.clamp(0.0, 1.0)))
+        .map(|z| z.id as u32)
+        .collect();
+    habitables.shuffle(&mut rng);
+    let placements_pnjs = pnj_ids.iter()
+        .enumerate()
+        .map(|(index, &pnj_id)| (pnj_id, habitables[index % habitables.len()]))
+        .collect();
+
+    MondeGenere { zones, placements_pnjs }
+}
+
+/// Crée une zone vide, sans nom ni connexion.
+fn zone_vide(id: u8) -> Zone {
+    Zone {
+        id,
+        nom: String::new(),
+        ouvert: false,
+        description: String::new(),
+        connection: vec![],
+        coffres: vec![],
+        objet_zone: Inventaire { taille: 255, objets: vec![] },
+        mob_present: false,
+        mob: None,
+        reapparition: None,
+        prix: 0,
+        tentatives_fouille: 0,
+        meteo: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moteur::charger_zones_depuis;
+
+    /// Vérifie qu'un monde généré est connexe, cohérent, reproductible et relisible.
+    #[test]
+    fn test_generer_monde() {
+        let modeles = Modeles::charger("src/json/generation.json").unwrap();
+        let parametres = ParametresGeneration { graine: 42, nombre_zones: 25, ..Default::default() };
+        let monde = generer(&parametres, &modeles, &[1, 2, 3]);
+        assert_eq!(monde.zones.len(), 25);
+        assert!(crate::export::anomalies(&monde.zones).is_empty());
+        assert!(distances(&monde.zones).iter().all(|d| *d != u32::MAX));
+        // Chaque connexion a son retour dans la direction opposée
+        for zone in &monde.zones {
+            for conn in &zone.connection {
+                let (_, _, opposee) = DIRECTIONS.iter().find(|(d, _, _)| *d == conn.direction).unwrap();
+                let dest = &monde.zones[conn.id_dest.parse::<usize>().unwrap() - 1];
+                assert!(dest.connection.iter().any(|c| c.direction == *opposee && c.id_dest == zone.id.to_string()));
+            }
+        }
+        assert_eq!(monde.zones[0].prix, 0);
+        assert!(monde.zones.iter().skip(1).all(|z| z.prix > 0 && !z.ouvert));
+        assert_eq!(monde.placements_pnjs.len(), 3);
+
+        let autre = generer(&parametres, &modeles, &[1, 2, 3]);
+        assert_eq!(autre.zones.iter().map(|z| &z.nom).collect::<Vec<_>>(), monde.zones.iter().map(|z| &z.nom).collect::<Vec<_>>());
+        assert_eq!(autre.placements_pnjs, monde.placements_pnjs);
+
+        // Le monde écrit est relu à l'identique, hormis le contenu des coffres
+        crate::moteur::charger_objets().unwrap();
+        // Des noms propres à ce processus évitent les collisions entre exécutions simultanées
+        let dossier = std::env::temp_dir();
+        let chemin_zones = dossier.join(format!("rustrpg_test_zone_{}.json", std::process::id()));
+        let chemin_coffres = dossier.join(format!("rustrpg_test_coffre_{}.json", std::process::id()));
+        crate::moteur::ecrire_zones(&monde.zones, chemin_zones.to_str().unwrap(), chemin_coffres.to_str().unwrap()).unwrap();
+        let mut relues = charger_zones_depuis(chemin_zones.to_str().unwrap(), chemin_coffres.to_str().unwrap()).unwrap();
+        relues.sort_by_key(|z| z.id);
+        assert_eq!(relues.len(), 25);
+        for (relue, zone) in relues.iter().zip(&monde.zones) {
+            assert_eq!((relue.id, &relue.nom, relue.prix, relue.mob_present), (zone.id, &zone.nom, zone.prix, zone.mob_present));
+            assert_eq!(relue.connection.len(), zone.connection.len());
+            assert_eq!(relue.coffres.len(), zone.coffres.len());
+        }
+        // Des coffres illisibles sont une erreur, pas un arrêt du jeu
+        std::fs::write(&chemin_coffres, "[{ \"id\": \"abc\" }]").unwrap();
+        assert!(charger_zones_depuis(chemin_zones.to_str().unwrap(), chemin_coffres.to_str().unwrap()).is_err());
+        std::fs::remove_file(&chemin_zones).unwrap();
+        std::fs::remove_file(&chemin_coffres).unwrap();
+    }
+}
@@ -1,20 +1,179 @@
 use serde::{Serialize, Deserialize};
 use std::cmp::Reverse;
 use std::fmt;
-use crate::objet::OBJETS_DISPONIBLES;
+use rand::Rng;
+use crate::objet::{Objet, OBJETS_DISPONIBLES, TAILLE_PILE_DEFAUT};
 use std::sync::RwLockReadGuard;
 use crate::affichage;
 
-/// Représente un inventaire avec une capacité maximale (`taille`)
+/// Représente un inventaire avec un nombre d'emplacements (`taille`)
 /// et une liste d'objets stockés (`objets`).
+///
+/// Chaque entrée de `objets` occupe un emplacement et contient une pile
+/// d'exemplaires d'un même objet, limitée par `taille_pile`.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Inventaire {
-    /// Capacité maximale de l'inventaire
+    /// Nombre maximal d'emplacements (piles) de l'inventaire
     pub taille: u8,
     /// Liste des objets présents dans l'inventaire
     pub objets: Vec<ObjetInventaire>,
 }
 
+/// Erreurs possibles lors de l'ajout d'objets dans un inventaire.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErreurInventaire {
+    /// Aucun emplacement libre ni aucune pile incomplète pour accueillir l'objet.
+    Plein,
+}
+
+impl fmt::Display for ErreurInventaire {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErreurInventaire::Plein => write!(f, "L'inventaire est plein"),
+        }
+    }
+}
+
+/// Renvoie le nombre maximal d'exemplaires d'un objet par emplacement,
+/// d'après le catalogue (`Objet::taille_pile`).
+///
+/// Les objets absents du catalogue utilisent `TAILLE_PILE_DEFAUT`.
+pub fn taille_pile(id: u8) -> u8 {
+    OBJETS_DISPONIBLES.read().unwrap()
+        .get(&id)
+        .map(|o| o.taille_pile())
+        .unwrap_or(TAILLE_PILE_DEFAUT)
+}
+
+/// Durabilité d'un exemplaire neuf ; un exemplaire est cassé quand elle tombe à 0.
+pub const DURABILITE_MAX: u8 = 100;
+/// Probabilité qu'un exemplaire trouvé de qualité supérieure à `Commune` soit enchanté.
+const CHANCE_AFFIXE: f64 = 0.5;
+
+/// Qualité d'un exemplaire, qui multiplie ses dégâts ou sa protection.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum Qualite {
+    #[default]
+    Commune,
+    Rare,
+    Epique,
+    Legendaire,
+}
+
+impl Qualite {
+    /// Multiplicateur appliqué aux dégâts ou à la protection de l'exemplaire.
+    pub fn multiplicateur(&self) -> f32 {
+        match self {
+            Qualite::Commune => 1.0,
+            Qualite::Rare => 1.25,
+            Qualite::Epique => 1.5,
+            Qualite::Legendaire => 2.0,
+        }
+    }
+
+    /// Tire une qualité au hasard, les meilleures étant les plus rares.
+    pub fn aleatoire(rng: &mut impl Rng) -> Self {
+        match rng.random_range(0..100) {
+            0..70 => Qualite::Commune,
+            70..90 => Qualite::Rare,
+            90..98 => Qualite::Epique,
+            _ => Qualite::Legendaire,
+        }
+    }
+}
+
+impl fmt::Display for Qualite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Qualite::Commune => write!(f, "commune"),
+            Qualite::Rare => write!(f, "rare"),
+            Qualite::Epique => write!(f, "épique"),
+            Qualite::Legendaire => write!(f, "légendaire"),
+        }
+    }
+}
+
+/// Enchantement porté par un exemplaire.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum Affixe {
+    /// Dégâts supplémentaires infligés par une arme.
+    Degats { bonus: u32 },
+    /// Protection supplémentaire apportée par un équipement.
+    Protection { bonus: u8 },
+}
+
+impl fmt::Display for Affixe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Affixe::Degats { bonus } => write!(f, "+{} dégâts", bonus),
+            Affixe::Protection { bonus } => write!(f, "+{} protection", bonus),
+        }
+    }
+}
+
+/// Propriétés propres à un exemplaire d'objet unique (arme ou équipement).
+///
+/// Un exemplaire sans `Instance` est un objet neuf, de qualité commune et sans enchantement.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Instance {
+    /// Nom donné par le joueur, affiché à la place du nom du catalogue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nom: Option<String>,
+    /// Durabilité restante, de 0 (cassé) à `DURABILITE_MAX`.
+    pub durabilite: u8,
+    /// Qualité de l'exemplaire.
+    #[serde(default)]
+    pub qualite: Qualite,
+    /// Enchantements de l'exemplaire.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub affixes: Vec<Affixe>,
+}
+
+impl Default for Instance {
+    fn default() -> Self {
+        Instance { nom: None, durabilite: DURABILITE_MAX, qualite: Qualite::Commune, affixes: vec![] }
+    }
+}
+
+impl Instance {
+    /// Tire au hasard un exemplaire trouvé dans le monde : usé, de qualité
+    /// variable et parfois enchanté.
+    ///
+    /// # Arguments
+    /// * `objet` - L'objet du catalogue dont on crée un exemplaire.
+    /// * `rng` - Le générateur aléatoire.
+    pub fn aleatoire(objet: &Objet, rng: &mut impl Rng) -> Self {
+        let qualite = Qualite::aleatoire(rng);
+        let mut affixes = vec![];
+        if qualite != Qualite::Commune && rng.random_bool(CHANCE_AFFIXE) {
+            affixes.push(if objet.est_arme() {
+                Affixe::Degats { bonus: rng.random_range(2..=8) }
+            } else {
+                Affixe::Protection { bonus: 1 }
+            });
+        }
+        Instance {
+            nom: None,
+            durabilite: rng.random_range(DURABILITE_MAX / 2..=DURABILITE_MAX),
+            qualite,
+            affixes,
+        }
+    }
+}
+
+impl fmt::Display for Instance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}/{}", self.qualite, self.durabilite, DURABILITE_MAX)?;
+        for affixe in &self.affixes {
+            write!(f, ", {}", affixe)?;
+        }
+        if self.durabilite == 0 {
+            write!(f, ", cassé")?;
+        }
+        Ok(())
+    }
+}
+
 /// Représente un objet dans l'inventaire avec un identifiant et une quantité.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ObjetInventaire {
@@ -22,6 +181,74 @@ pub struct ObjetInventaire {
     pub nombre : u8,
     /// Identifiant unique de l'objet
     pub objet_id: u8,
+    /// Propriétés propres à l'exemplaire ; une pile n'en a jamais.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<Instance>,
+}
+
+impl ObjetInventaire {
+    /// Nom affiché : nom personnalisé ou nom du catalogue, suivi des propriétés de l'exemplaire.
+    ///
+    /// # Arguments
+    /// * `objet` - L'objet du catalogue correspondant.
+    pub fn nom_affiche(&self, objet: &Objet) -> String {
+        match &self.instance {
+            Some(instance) => format!("{} [{}]", instance.nom.as_deref().unwrap_or(&objet.nom), instance),
+            None => objet.nom.clone(),
+        }
+    }
+
+    /// Vérifie si l'exemplaire est cassé : il n'a alors plus aucun effet.
+    pub fn est_casse(&self) -> bool {
+        self.instance.as_ref().is_some_and(|i| i.durabilite == 0)
+    }
+
+    /// Dégâts infligés par l'exemplaire d'une arme, qualité et enchantements compris.
+    ///
+    /// # Arguments
+    /// * `base` - Dégâts de l'arme dans le catalogue.
+    pub fn degats(&self, base: u32) -> u32 {
+        let Some(instance) = &self.instance else {
+            return base;
+        };
+        let bonus: u32 = instance.affixes.iter()
+            .map(|a| match a { Affixe::Degats { bonus } => *bonus, _ => 0 })
+            .sum();
+        (base as f32 * instance.qualite.multiplicateur()) as u32 + bonus
+    }
+
+    /// Protection apportée par l'exemplaire d'un équipement, qualité et enchantements compris.
+    ///
+    /// # Arguments
+    /// * `base` - Protection de l'équipement dans le catalogue.
+    pub fn protection(&self, base: u8) -> u32 {
+        let Some(instance) = &self.instance else {
+            return base as u32;
+        };
+        let bonus: u32 = instance.affixes.iter()
+            .map(|a| match a { Affixe::Protection { bonus } => *bonus as u32, _ => 0 })
+            .sum();
+        (base as f32 * instance.qualite.multiplicateur()) as u32 + bonus
+    }
+
+    /// Fait perdre un point de durabilité à l'exemplaire.
+    ///
+    /// # Retour
+    /// `true` si l'exemplaire vient de se casser.
+    pub fn abimer(&mut self) -> bool {
+        let instance = self.instance.get_or_insert_with(Instance::default);
+        if instance.durabilite == 0 {
+            return false;
+        }
+        instance.durabilite -= 1;
+        instance.durabilite == 0
+    }
+
+    /// Donne un nom personnalisé à l'exemplaire ; un nom vide rétablit celui du catalogue.
+    pub fn renommer(&mut self, nom: &str) {
+        let nom = nom.trim();
+        self.instance.get_or_insert_with(Instance::default).nom = (!nom.is_empty()).then(|| nom.to_string());
+    }
 }
 
 impl Inventaire {
@@ -31,7 +258,7 @@ impl Inventaire {
     /// - `est_joueur` : true si l'inventaire appartient au joueur.
     /// - `zone` : la zone actuelle.
     /// - `pnjs` : PNJs présents pour notification.
-    pub fn afficher(&mut self, est_joueur : bool, zone: &crate::zone::Zone, pnjs: &Vec<crate::personnage::PNJ>) -> Option<usize> {
+    pub fn afficher(&mut self, est_joueur : bool, zone: &crate::zone::Zone, pnjs: &[crate::personnage::PNJ]) -> Option<usize> {
         if self.objets.is_empty(){
             if est_joueur {
                 affichage::notifier(zone, "📦 Votre inventaire est vide", pnjs);
@@ -41,12 +268,12 @@ impl Inventaire {
             }
             return None
         }
-        let mut message = format!("📦 Inventaire (Taille: {}):\n", self.taille);
+        let mut message = format!("📦 Inventaire (Emplacements: {}/{}):\n", self.objets.len(), self.taille);
         let objets_all: RwLockReadGuard<_> = OBJETS_DISPONIBLES.read().unwrap();
         self.trier_quantite();
         for (index, obj) in self.objets.iter().enumerate() {
             if let Some(o) = objets_all.get(&obj.objet_id) {
-                message.push_str(&format!("  {} : {} (x{})\n", index + 1, o.nom, obj.nombre));
+                message.push_str(&format!("  {} : {} (x{})\n", index + 1, obj.nom_affiche(o), obj.nombre));
             } else {
                 message.push_str(&format!("  Objet inconnu (ID: {})\n", obj.objet_id));
             }
@@ -109,19 +336,19 @@ impl Inventaire {
     /// - `zone` : zone d’origine.
     /// - `joueur` : personnage du joueur qui récupère les objets.
     /// - `pnjs` : PNJs pour feedback.
-    pub fn afficher_inventaire_zone_et_coffre(&mut self, zone: &crate::zone::Zone, joueur: &mut crate::personnage::Personnage, pnjs: &Vec<crate::personnage::PNJ>) -> Option<()> {
+    pub fn afficher_inventaire_zone_et_coffre(&mut self, zone: &crate::zone::Zone, joueur: &mut crate::personnage::Personnage, pnjs: &[crate::personnage::PNJ]) -> Option<()> {
         use std::io;
 
         if self.objets.is_empty() {
             affichage::notifier(zone, "📦 Malheureusement c'est vide", pnjs);
             return None
         }
-        let mut message = format!("📦 Inventaire (Taille: {}):\n", self.taille);
+        let mut message = format!("📦 Inventaire (Emplacements: {}/{}):\n", self.objets.len(), self.taille);
         let objets_all: RwLockReadGuard<_> = OBJETS_DISPONIBLES.read().unwrap();
         self.trier_quantite();
         for (index, obj) in self.objets.iter().enumerate() {
             if let Some(o) = objets_all.get(&obj.objet_id) {
-                message.push_str(&format!("  {} : {} (x{})\n", index + 1, o.nom, obj.nombre));
+                message.push_str(&format!("  {} : {} (x{})\n", index + 1, obj.nom_affiche(o), obj.nombre));
             } else {
                 message.push_str(&format!("  Objet inconnu (ID: {})\n", obj.objet_id));
             }
@@ -151,29 +378,26 @@ impl Inventaire {
             }
         };
 
-        // 5. Vérification de la place dans l'inventaire du joueur
-        let place_actuelle: u8 = joueur
-            .inventaire
-            .objets
-            .iter()
-            .map(|o| o.nombre)
-            .sum();
-        if place_actuelle + qty > joueur.inventaire.taille {
-            affichage::notifier(zone, "❌ Pas assez de place dans votre inventaire !", pnjs);
+        let obj_id = self.objets[index].objet_id;
+        // Vérification du poids que le joueur peut encore porter
+        let poids_unitaire = objets_all.get(&obj_id).map(|o| o.poids).unwrap_or(0);
+        if !joueur.peut_porter(poids_unitaire * qty as u32) {
+            affichage::notifier(zone, "❌ C'est trop lourd, vous ne pouvez pas porter autant !", pnjs);
             return None;
         }
 
-        // 6. On retire qty fois de la zone et on ajoute dans l'inventaire du joueur
-        let obj_id = self.objets[index].objet_id;
-        for _ in 0..qty {
-            let retire = self.récupérer_objet(index);
-            joueur.inventaire.ajouter_objet(retire as u8);
+        // 6. On ajoute les exemplaires dans l'inventaire du joueur puis on les retire de la zone
+        let exemplaire = ObjetInventaire { nombre: qty, ..self.objets[index].clone() };
+        if joueur.inventaire.ajouter_exemplaire(exemplaire).is_err() {
+            affichage::notifier(zone, "❌ Pas assez de place dans votre inventaire !", pnjs);
+            return None;
         }
+        let exemplaire = self.retirer_exemplaires(index, qty);
 
         // 7. Notification finale
         let nom = objets_all
             .get(&obj_id)
-            .map(|o| o.nom.clone())
+            .map(|o| exemplaire.nom_affiche(o))
             .unwrap_or_else(|| format!("ID {}", obj_id));
         let msg = format!("✅ Vous récupérez {} x{}", nom, qty);
         affichage::notifier(zone, &msg, pnjs);
@@ -181,23 +405,101 @@ impl Inventaire {
         Some(())
     }
 
-    /// Ajoute un objet à l'inventaire en augmentant la quantité si présent,
-    /// ou en l'ajoutant s'il est nouveau.
+    /// Ajoute un objet neuf à l'inventaire : complète d'abord une pile existante
+    /// du même objet, sinon occupe un nouvel emplacement.
+    ///
+    /// Retourne `Err(ErreurInventaire::Plein)` si toutes les piles de cet objet
+    /// sont pleines et qu'il ne reste aucun emplacement libre.
     ///
     /// - `id` : identifiant de l'objet à ajouter.
-    pub fn ajouter_objet(&mut self, id: u8){
-        for objet in &mut self.objets {
-            if objet.objet_id == id {
-                objet.nombre += 1;
-                return
-            }
+    pub fn ajouter_objet(&mut self, id: u8) -> Result<(), ErreurInventaire> {
+        let pile_max = taille_pile(id);
+        if let Some(objet) = self.objets.iter_mut().find(|o| o.objet_id == id && o.instance.is_none() && o.nombre < pile_max) {
+            objet.nombre += 1;
+            return Ok(());
+        }
+        if self.objets.len() >= self.taille as usize {
+            return Err(ErreurInventaire::Plein);
         }
         let new_obj = ObjetInventaire {
             nombre : 1,
             objet_id : id,
+            instance: None,
         };
         self.objets.insert(0, new_obj);
         self.trier_quantite();
+        Ok(())
+    }
+
+    /// Ajoute `quantite` exemplaires d'un objet, ou aucun s'il n'y a pas la place
+    /// pour tous.
+    ///
+    /// - `id` : identifiant de l'objet à ajouter.
+    /// - `quantite` : nombre d'exemplaires à ajouter.
+    pub fn ajouter_objets(&mut self, id: u8, quantite: u8) -> Result<(), ErreurInventaire> {
+        if !self.peut_ajouter(id, quantite) {
+            return Err(ErreurInventaire::Plein);
+        }
+        for _ in 0..quantite {
+            self.ajouter_objet(id)?;
+        }
+        Ok(())
+    }
+
+    /// Vérifie si `quantite` exemplaires d'un objet peuvent être ajoutés, en comptant
+    /// la place restante dans les piles existantes et dans les emplacements libres.
+    ///
+    /// - `id` : identifiant de l'objet.
+    /// - `quantite` : nombre d'exemplaires à ajouter.
+    pub fn peut_ajouter(&self, id: u8, quantite: u8) -> bool {
+        let pile_max = taille_pile(id) as u32;
+        let place_dans_piles: u32 = self.objets.iter()
+            .filter(|o| o.objet_id == id && o.instance.is_none())
+            .map(|o| pile_max.saturating_sub(o.nombre as u32))
+            .sum();
+        let place_nouvelles_piles = self.emplacements_libres() as u32 * pile_max;
+        quantite as u32 <= place_dans_piles + place_nouvelles_piles
+    }
+
+    /// Ajoute des exemplaires en conservant leurs propriétés : chaque exemplaire avec
+    /// une `Instance` occupe son propre emplacement, les autres s'empilent comme
+    /// avec `ajouter_objets`.
+    ///
+    /// Rien n'est ajouté s'il n'y a pas la place pour tous les exemplaires.
+    ///
+    /// - `exemplaire` : objet et nombre d'exemplaires à ajouter.
+    pub fn ajouter_exemplaire(&mut self, exemplaire: ObjetInventaire) -> Result<(), ErreurInventaire> {
+        if exemplaire.instance.is_none() {
+            return self.ajouter_objets(exemplaire.objet_id, exemplaire.nombre);
+        }
+        if self.emplacements_libres() < exemplaire.nombre as usize {
+            return Err(ErreurInventaire::Plein);
+        }
+        for _ in 0..exemplaire.nombre {
+            self.objets.insert(0, ObjetInventaire { nombre: 1, ..exemplaire.clone() });
+        }
+        self.trier_quantite();
+        Ok(())
+    }
+
+    /// Retire `quantite` exemplaires de l'emplacement donné, sans réordonner les autres.
+    ///
+    /// Retourne les exemplaires retirés, avec leurs propriétés.
+    ///
+    /// - `index` : position de l'objet dans la liste.
+    /// - `quantite` : nombre d'exemplaires à retirer, au plus la taille de la pile.
+    pub fn retirer_exemplaires(&mut self, index: usize, quantite: u8) -> ObjetInventaire {
+        let exemplaires = ObjetInventaire { nombre: quantite, ..self.objets[index].clone() };
+        self.objets[index].nombre -= quantite;
+        if self.objets[index].nombre == 0 {
+            self.objets.remove(index);
+        }
+        exemplaires
+    }
+
+    /// Renvoie le nombre d'emplacements encore libres dans l'inventaire.
+    pub fn emplacements_libres(&self) -> usize {
+        (self.taille as usize).saturating_sub(self.objets.len())
     }
 
     /// Récupère un objet de l'inventaire à l'index donné et diminue la quantité.
@@ -232,23 +534,36 @@ impl Inventaire {
         objet
     }
 
-    /// Retire un objet de l’inventaire par son identifiant.
+    /// Retire un objet de l’inventaire par son identifiant, de préférence un
+    /// exemplaire ordinaire plutôt qu'un exemplaire personnalisé.
     ///
     /// Retourne `true` si l’objet a été trouvé et retiré.
     ///
     /// - `id` : identifiant de l’objet
     pub fn retirer_par_id(&mut self, id: u8) -> bool {
-        for i in 0..self.objets.len() {
-            if self.objets[i].objet_id == id {
-                self.objets[i].nombre -= 1;
-                if self.objets[i].nombre == 0 {
-                    self.objets.remove(i);
-                }
-                self.trier_quantite();
-                return true;
-            }
+        let index = self.objets.iter().position(|o| o.objet_id == id && o.instance.is_none())
+            .or_else(|| self.objets.iter().position(|o| o.objet_id == id));
+        let Some(i) = index else {
+            return false;
+        };
+        self.objets[i].nombre -= 1;
+        if self.objets[i].nombre == 0 {
+            self.objets.remove(i);
         }
-        false
+        self.trier_quantite();
+        true
+    }
+
+    /// Calcule le poids total (en grammes) des objets de l'inventaire
+    /// à partir du catalogue `OBJETS_DISPONIBLES`.
+    ///
+    /// Les objets absents du catalogue sont comptés comme ne pesant rien.
+    pub fn poids_total(&self) -> u32 {
+        let objets_all = OBJETS_DISPONIBLES.read().unwrap();
+        self.objets
+            .iter()
+            .map(|obj| objets_all.get(&obj.objet_id).map(|o| o.poids * obj.nombre as u32).unwrap_or(0))
+            .sum()
     }
 
     /// Trie les objets dans l'inventaire par quantité décroissante.
@@ -264,7 +579,7 @@ impl fmt::Display for Inventaire {
         } else {
             for objet in &self.objets {
                 if let Some(o) = OBJETS_DISPONIBLES.read().unwrap().get(&(objet.objet_id)){
-                    writeln!(f, "    - {}", o.nom)?;
+                    writeln!(f, "    - {}", objet.nom_affiche(o))?;
                 }
             }
         }
@@ -275,28 +590,102 @@ impl fmt::Display for Inventaire {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::outils_test::{catalogue_test, ENCLUME, HACHE, INCONNU, SABRE};
 
     #[test]
     fn test_ajouter_et_recuperer_objet() {
         let mut inv = Inventaire { taille: 5, objets: vec![] };
-        inv.ajouter_objet(1);
+        inv.ajouter_objet(INCONNU).unwrap();
         assert_eq!(inv.objets.len(), 1);
         assert_eq!(inv.objets[0].nombre, 1);
-        inv.ajouter_objet(1);
+        inv.ajouter_objet(INCONNU).unwrap();
         assert_eq!(inv.objets[0].nombre, 2);
         let id = inv.récupérer_objet(0);
-        assert_eq!(id, 1);
+        assert_eq!(id, INCONNU as usize);
         assert_eq!(inv.objets[0].nombre, 1);
     }
 
     #[test]
     fn test_trier_quantite() {
         let mut inv = Inventaire { taille: 5, objets: vec![
-            ObjetInventaire { nombre: 1, objet_id: 2 },
-            ObjetInventaire { nombre: 3, objet_id: 1 },
+            ObjetInventaire { nombre: 1, objet_id: 2, instance: None },
+            ObjetInventaire { nombre: 3, objet_id: 1, instance: None },
         ]};
         inv.trier_quantite();
         assert_eq!(inv.objets[0].objet_id, 1);
         assert_eq!(inv.objets[0].nombre, 3);
     }
+
+    #[test]
+    fn test_poids_total() {
+        catalogue_test();
+        let inv = Inventaire { taille: 5, objets: vec![
+            ObjetInventaire { nombre: 2, objet_id: ENCLUME, instance: None },
+            ObjetInventaire { nombre: 1, objet_id: INCONNU, instance: None },
+        ]};
+        assert_eq!(inv.poids_total(), 3000);
+    }
+
+    #[test]
+    fn test_capacite_emplacements_et_piles() {
+        catalogue_test();
+        let mut inv = Inventaire { taille: 2, objets: vec![] };
+        // Les armes sont uniques : chaque hache occupe son propre emplacement
+        assert!(inv.peut_ajouter(HACHE, 2));
+        assert!(!inv.peut_ajouter(HACHE, 3));
+        assert_eq!(inv.ajouter_objets(HACHE, 3), Err(ErreurInventaire::Plein));
+        assert!(inv.objets.is_empty());
+        inv.ajouter_objets(HACHE, 2).unwrap();
+        assert_eq!(inv.objets.len(), 2);
+        assert_eq!(inv.emplacements_libres(), 0);
+        assert_eq!(inv.ajouter_objet(HACHE), Err(ErreurInventaire::Plein));
+        assert_eq!(inv.ajouter_objet(INCONNU), Err(ErreurInventaire::Plein));
+    }
+
+    #[test]
+    fn test_exemplaires_uniques() {
+        catalogue_test();
+        // Les inventaires sauvegardés avant les exemplaires restent lisibles et inchangés
+        let ancien: ObjetInventaire = serde_json::from_str(r#"{"nombre":1,"objet_id":214}"#).unwrap();
+        assert!(ancien.instance.is_none());
+        assert_eq!(serde_json::to_string(&ancien).unwrap(), r#"{"nombre":1,"objet_id":214}"#);
+
+        let mut sabre = ObjetInventaire {
+            nombre: 1,
+            objet_id: SABRE,
+            instance: Some(Instance {
+                nom: None,
+                durabilite: 1,
+                qualite: Qualite::Rare,
+                affixes: vec![Affixe::Degats { bonus: 3 }],
+            }),
+        };
+        sabre.renommer("Croc");
+        assert_eq!(sabre.degats(10), 15);
+        let relu: ObjetInventaire = serde_json::from_str(&serde_json::to_string(&sabre).unwrap()).unwrap();
+        assert_eq!(relu.instance, sabre.instance);
+
+        let mut inv = Inventaire { taille: 3, objets: vec![] };
+        inv.ajouter_exemplaire(sabre.clone()).unwrap();
+        inv.ajouter_objet(SABRE).unwrap();
+        assert_eq!(inv.objets.len(), 2);
+        // Le retrait par identifiant épargne l'exemplaire personnalisé
+        inv.retirer_par_id(SABRE);
+        assert_eq!(inv.objets[0].instance, sabre.instance);
+
+        // Plusieurs exemplaires personnalisés occupent chacun leur emplacement, ou aucun
+        let paire = ObjetInventaire { nombre: 2, ..sabre.clone() };
+        inv.taille = 2;
+        assert_eq!(inv.ajouter_exemplaire(paire.clone()), Err(ErreurInventaire::Plein));
+        assert_eq!(inv.objets.len(), 1);
+        inv.taille = 3;
+        inv.ajouter_exemplaire(paire).unwrap();
+        assert_eq!(inv.objets.len(), 3);
+        assert!(inv.objets.iter().all(|o| o.nombre == 1 && o.instance == sabre.instance));
+
+        assert!(sabre.abimer());
+        assert!(sabre.est_casse());
+        let objets = OBJETS_DISPONIBLES.read().unwrap();
+        assert_eq!(sabre.nom_affiche(&objets[&SABRE]), "Croc [rare, 0/100, +3 dégâts, cassé]");
+    }
 }
@@ -6,23 +6,39 @@ mod objet;
 mod personnage;
 mod affichage;
 mod combat;
+mod progression;
+mod dialogue;
+mod quete;
+mod classe;
+mod evenement;
+mod carte;
+mod export;
+mod generation;
+mod reserve;
+mod artisanat;
+#[cfg(test)]
+mod outils_test;
 
 use std::io;
 use zone::Zone;
 use moteur::{charger_zones};
 use rand::Rng;
-use crate::moteur::charger_objets;
+use crate::moteur::{charger_objets, charger_quetes};
+use crate::quete::Evenement;
 use std::thread::sleep;
 use std::time::Duration;
-use rand::seq::IndexedRandom;
 use inventaire::Inventaire;
 use personnage::Joueur;
 use personnage::Personnage;
 use personnage::PNJ;
 use personnage::Mob;
-use crate::combat::combattre;
+use personnage::Caracteristique;
+use crate::progression::COURBE_PROGRESSION;
+use crate::classe::Classe;
+use crate::evenement::EvenementAleatoire;
+use crate::combat::ResultatAffrontement;
 use crate::inventaire::ObjetInventaire;
-use crate::objet::{Emplacement, OBJETS_DISPONIBLES};
+use crate::objet::{Emplacement, TypeObjet, OBJETS_DISPONIBLES};
 use chrono::Utc;
 
 /// Gère le déplacement du joueur d'une zone à une autre.
@@ -41,19 +57,25 @@ use chrono::Utc;
 ///
 /// # Comportement
 ///
+/// - Si la connexion a des exigences non remplies (clé, drapeau, quête, niveau, sens unique),
+///   le déplacement est refusé et la raison est affichée.
 /// - Si la zone cible contient un ennemi (`mob_present`), le joueur doit confirmer s’il souhaite y entrer.
 /// - Si la zone n’est pas ouverte, le joueur peut l’acheter s’il a assez d’argent.
 /// - Si toutes les conditions sont remplies, le joueur est déplacé et une animation (temporisation) est affichée.
-fn se_deplacer(zones: &mut Vec<Zone>, current_zone_index: &mut usize, direction: &str, perso_joueur: &mut Personnage, pnjs: &Vec<PNJ>) {
+fn se_deplacer(zones: &mut [Zone], current_zone_index: &mut usize, direction: &str, perso_joueur: &mut Personnage, pnjs: &[PNJ]) {
     let current_zone = &zones[*current_zone_index];
 
     // Trouver la connexion
     if let Some(conn) = current_zone.connection.iter().find(|c| c.direction == direction) {
+        if let Err(raison) = conn.verifier_acces(perso_joueur) {
+            affichage::notifier(current_zone, &raison, pnjs);
+            return;
+        }
         // Trouver la nouvelle zone via l'ID de la connexion
         if let Some(new_index) = zones.iter().position(|z| z.id == conn.id_dest.parse::<u8>().unwrap()) {
-            if zones[new_index].mob_present {
+            if zones[new_index].mob.is_some() {
                 let mob_choix = affichage::faire_choix(
-                    &format!("Il y a un ennemie dans la zone {}, il se peut qu'il vous attaque ,voulez-vous y aller quand même ? (oui/non)", conn.id_dest),
+                    &format!("Il y a un ennemie dans la zone {}, il se peut qu'il vous attaque ,voulez-vous y aller quand même ? (oui/non)", zones[new_index].nom_pour(perso_joueur)),
                     &vec!["oui".to_string(), "non".to_string()]
                 );
                 match mob_choix.as_str() {
@@ -61,26 +83,27 @@ fn se_deplacer(zones: &mut Vec<Zone>, current_zone_index: &mut usize, direction:
                         //println!("Début du combat");
                     }
                     _ => {
-                        let msg = format!("Vous avez peur de l'ennemie, vous restez dans la même zone");
-                        affichage::notifier(&zones[*current_zone_index], &msg, &pnjs);
+                        let msg = "Vous avez peur de l'ennemie, vous restez dans la même zone".to_string();
+                        affichage::notifier(&zones[*current_zone_index], &msg, pnjs);
                         return
                     }
                 }
             }
             else {
-                let msg = format!("Il y a aucun mob");
-                affichage::notifier(&zones[*current_zone_index], &msg, &pnjs);
+                let msg = "Il y a aucun mob".to_string();
+                affichage::notifier(&zones[*current_zone_index], &msg, pnjs);
             }
             if zones[new_index].ouvert {
                 *current_zone_index = new_index; // Mise à jour de l'index
-                affichage::notifier(&zones[*current_zone_index], "Déplacement...", &pnjs);
-                sleep(Duration::from_secs(5));
-                affichage::notifier(&zones[*current_zone_index],"Vous êtes arrivés dans la zone", &pnjs);
+                notifier_deplacement(&zones[*current_zone_index], perso_joueur, pnjs);
+                sleep(perso_joueur.duree_deplacement());
+                affichage::notifier(&zones[*current_zone_index],"Vous êtes arrivés dans la zone", pnjs);
+                quete::signaler(perso_joueur, &Evenement::ZoneAtteinte(zones[*current_zone_index].id));
             }
             else {
                 let prix_zone = zones[new_index].prix;
                 let choix = affichage::faire_choix(
-                    &format!("La zone {} n'est pas ouverte (prix : {} pièces, votre solde : {} pièces), voulez-vous l'acheter ? (oui/non)", conn.id_dest, prix_zone, perso_joueur.argent),
+                    &format!("La zone {} n'est pas ouverte (prix : {} pièces, votre solde : {} pièces), voulez-vous l'acheter ? (oui/non)", zones[new_index].nom_pour(perso_joueur), prix_zone, perso_joueur.argent),
                     &vec!["oui".to_string(), "non".to_string()]
                 );
                 match choix.as_str() {
@@ -88,37 +111,271 @@ fn se_deplacer(zones: &mut Vec<Zone>, current_zone_index: &mut usize, direction:
                         if perso_joueur.argent >= prix_zone {
                             perso_joueur.retirer_argent(prix_zone);
                             zones[new_index].ouvert = true;
+                            perso_joueur.progression.acheter_zone(zones[new_index].id);
+                            affichage::ajouter_notification(&format!(
+                                "🏠 Vous possédez désormais {} : elle vous rapportera {} pièces régulièrement, vous pourrez vous y reposer et ses marchands vous feront un prix.",
+                                zones[new_index].nom, zones[new_index].revenu()
+                            ));
                             *current_zone_index = new_index;
-                            affichage::notifier(&zones[*current_zone_index], "Déplacement...", &pnjs);
-                            sleep(Duration::from_secs(5));
-                            affichage::notifier(&zones[*current_zone_index],"Vous êtes arrivés dans la zone", &pnjs);
+                            notifier_deplacement(&zones[*current_zone_index], perso_joueur, pnjs);
+                            sleep(perso_joueur.duree_deplacement());
+                            affichage::notifier(&zones[*current_zone_index],"Vous êtes arrivés dans la zone", pnjs);
+                            quete::signaler(perso_joueur, &Evenement::ZoneAtteinte(zones[*current_zone_index].id));
                         } else {
-                            affichage::notifier(&zones[*current_zone_index], "❌ Vous n'avez pas assez d'argent pour acheter cette zone !", &pnjs);
+                            affichage::notifier(&zones[*current_zone_index], "❌ Vous n'avez pas assez d'argent pour acheter cette zone !", pnjs);
                         }
                     }
                     _ => {
-                        affichage::notifier(&zones[*current_zone_index], "Zone non achetée, vous restez dans la même zone", &pnjs);
+                        affichage::notifier(&zones[*current_zone_index], "Zone non achetée, vous restez dans la même zone", pnjs);
                     }
                 }
             }
         } else {
-            affichage::notifier(&zones[*current_zone_index], "⚠️ La zone de destination n'a pas été trouvée !", &pnjs);
+            affichage::notifier(&zones[*current_zone_index], "⚠️ La zone de destination n'a pas été trouvée !", pnjs);
+        }
+    } else {
+        affichage::notifier(&zones[*current_zone_index], "❌ Vous êtes arrivé au bout du monde, faites demi-tour !", pnjs);
+    }
+}
+
+/// Issue de l'arrivée du joueur dans une zone.
+#[derive(Debug, PartialEq)]
+enum IssueArrivee {
+    /// Rien ne s'est passé.
+    Paisible,
+    /// Un mob a attaqué ou un événement s'est produit.
+    Rencontre,
+    /// Le joueur est mort.
+    Mort,
+}
+
+/// Gère l'arrivée du joueur dans une nouvelle zone : le mob de la zone quittée
+/// peut le suivre, le mob présent peut l'attaquer selon son agressivité, puis un
+/// événement aléatoire de la zone peut survenir.
+fn arriver_dans_zone(zones: &mut [Zone], zone_depart: usize, current_zone_index: usize, perso_joueur: &mut Personnage, pnjs: &mut [PNJ], mobs: &[Mob], evenements: &[EvenementAleatoire]) -> IssueArrivee {
+    let mut issue = IssueArrivee::Paisible;
+    if let Some(mob_id) = zone::poursuivre_joueur(zones, zone_depart, current_zone_index, mobs) {
+        if let Some(mob) = mobs.iter().find(|m| m.personnage.id == mob_id) {
+            let msg = format!("🐾 {} vous a suivi !", mob.personnage.nom);
+            affichage::notifier(&zones[current_zone_index], &msg, pnjs);
+        }
+    }
+    let mob_present = zones[current_zone_index].mob.and_then(|id| mobs.iter().find(|m| m.personnage.id == id));
+    if let Some(mob) = mob_present {
+        if rand::rng().random_bool(mob.agressivite.chance_attaque()) {
+            issue = IssueArrivee::Rencontre;
+            match combat::affronter_mob(perso_joueur, &mob.personnage, &zones[current_zone_index], pnjs) {
+                ResultatAffrontement::Victoire => zones[current_zone_index].retirer_mob(Utc::now()),
+                ResultatAffrontement::Fuite => {}
+                ResultatAffrontement::Defaite => return IssueArrivee::Mort,
+            }
+        } else {
+            let msg = format!("Vous êtes chanceux, {} ne vous attaque pas.", mob.personnage.nom);
+            affichage::notifier(&zones[current_zone_index], &msg, pnjs);
+        }
+    }
+    if let Some(evenement) = evenement::tirer(evenements, zones[current_zone_index].id) {
+        if !evenement::declencher(evenement, perso_joueur, zones, current_zone_index, pnjs, mobs) {
+            return IssueArrivee::Mort;
+        }
+        issue = IssueArrivee::Rencontre;
+    }
+    issue
+}
+
+/// Fait voyager le joueur vers une zone déjà visitée par le plus court chemin connu.
+///
+/// Chaque étape est plus rapide qu'un déplacement normal, mais les rencontres y
+/// sont vérifiées : un combat ou un événement interrompt le voyage. L'arrivée n'est
+/// annoncée que si le joueur s'est réellement déplacé.
+///
+/// # Retour
+/// `false` si le joueur est mort en route.
+fn voyager(zones: &mut [Zone], current_zone_index: &mut usize, destination: u8, perso_joueur: &mut Personnage, pnjs: &mut [PNJ], mobs: &[Mob], evenements: &[EvenementAleatoire]) -> bool {
+    let Some(chemin) = zone::chemin_le_plus_court(zones, *current_zone_index, destination, perso_joueur) else {
+        affichage::notifier(&zones[*current_zone_index], "❌ Aucun chemin connu ne mène à cette zone.", pnjs);
+        return true;
+    };
+    if chemin.is_empty() {
+        affichage::notifier(&zones[*current_zone_index], "Vous êtes déjà dans cette zone.", pnjs);
+        return true;
+    }
+    for etape in chemin {
+        let zone_depart = *current_zone_index;
+        notifier_deplacement(&zones[zone_depart], perso_joueur, pnjs);
+        sleep(perso_joueur.duree_deplacement() / 2);
+        *current_zone_index = etape;
+        perso_joueur.progression.decouvrir_zone(zones[etape].id, &zones[etape].destinations());
+        let msg = format!("🧭 Vous traversez {}.", zones[etape].nom);
+        affichage::notifier(&zones[etape], &msg, pnjs);
+        quete::signaler(perso_joueur, &Evenement::ZoneAtteinte(zones[etape].id));
+        match arriver_dans_zone(zones, zone_depart, etape, perso_joueur, pnjs, mobs, evenements) {
+            IssueArrivee::Paisible => {}
+            IssueArrivee::Rencontre => {
+                if zones[etape].id != destination {
+                    affichage::notifier(&zones[etape], "⛔ Votre voyage est interrompu.", pnjs);
+                }
+                return true;
+            }
+            IssueArrivee::Mort => return false,
         }
+    }
+    affichage::notifier(&zones[*current_zone_index], "Vous êtes arrivés à destination", pnjs);
+    true
+}
+
+/// Affiche la notification de départ d'un déplacement, en signalant au joueur
+/// qu'il est ralenti s'il porte plus que sa charge maximale.
+fn notifier_deplacement(zone: &Zone, perso_joueur: &Personnage, pnjs: &[PNJ]) {
+    if perso_joueur.est_surcharge() {
+        affichage::notifier(zone, "🐢 Déplacement... Vous êtes surchargé, vous avancez lentement !", pnjs);
     } else {
-        affichage::notifier(&zones[*current_zone_index], "❌ Vous êtes arrivé au bout du monde, faites demi-tour !", &pnjs);
+        affichage::notifier(zone, "Déplacement...", pnjs);
+    }
+}
+
+/// Équipe l'objet d'index `obj` de l'inventaire du joueur sur la partie du corps `index_partie`.
+///
+/// Si la partie porte déjà un objet, propose de les échanger : l'ancien objet retourne
+/// dans l'inventaire, ou est posé au sol de la zone si l'inventaire est plein. Si
+/// l'échange est impossible, l'objet reste dans l'inventaire.
+///
+/// # Arguments
+///
+/// * `perso_joueur` - Le personnage du joueur (modifiable).
+/// * `obj` - Index de l'objet dans l'inventaire du joueur.
+/// * `index_partie` - Index de la partie du corps à équiper.
+/// * `zone` - La zone actuelle, qui reçoit l'ancien objet si l'inventaire est plein.
+fn equiper_objet(perso_joueur: &mut Personnage, obj: usize, index_partie: usize, zone: &mut Zone) {
+    if !perso_joueur.parties_du_corps[index_partie].equipement().objets.is_empty() {
+        let new_choix = affichage::faire_choix(
+            "Equipement plein, voulez vous inverser l'objet ? (oui ou non)",
+            &vec!["oui".to_string(), "non".to_string()]
+        );
+        if new_choix.as_str() != "oui" {
+            return;
+        }
+    }
+    match perso_joueur.equiper(obj, index_partie, &mut zone.objet_zone) {
+        Ok(false) => println!("Equipement équipé !"),
+        Ok(true) => println!("Equipement équipé ! Inventaire plein, l'ancien équipement est posé au sol."),
+        Err(e) => println!("❌ {}", e),
+    }
+}
+
+/// Donne un nom personnalisé à une arme ou un équipement de l'inventaire.
+///
+/// Un exemplaire d'une ancienne pile est d'abord séparé des autres, s'il reste
+/// un emplacement libre.
+///
+/// # Arguments
+///
+/// * `perso_joueur` - Le personnage du joueur (modifiable).
+/// * `obj` - Index de l'objet dans l'inventaire.
+fn renommer_objet(perso_joueur: &mut Personnage, obj: usize) {
+    let id = perso_joueur.inventaire.objets[obj].objet_id;
+    if !OBJETS_DISPONIBLES.read().unwrap().get(&id).is_some_and(|o| o.est_unique()) {
+        println!("Seuls les armes et les équipements peuvent être renommés.");
+        return;
+    }
+    if perso_joueur.inventaire.objets[obj].nombre > 1 && perso_joueur.inventaire.emplacements_libres() == 0 {
+        println!("Il faut un emplacement libre pour séparer cet exemplaire des autres.");
+        return;
+    }
+    println!("Entrez le nouveau nom (laisser vide pour reprendre le nom d'origine) :");
+    let mut nom = String::new();
+    io::stdin().read_line(&mut nom).expect("❌ Erreur de lecture !");
+    let avant = perso_joueur.inventaire.clone();
+    let mut exemplaire = perso_joueur.inventaire.retirer_exemplaires(obj, 1);
+    exemplaire.renommer(&nom);
+    let nom_affiche = OBJETS_DISPONIBLES.read().unwrap().get(&id).map(|o| exemplaire.nom_affiche(o));
+    if let Err(e) = perso_joueur.inventaire.ajouter_exemplaire(exemplaire) {
+        perso_joueur.inventaire = avant;
+        println!("❌ {}, l'objet garde son nom.", e);
+        return;
+    }
+    if let Some(nom_affiche) = nom_affiche {
+        println!("✏️ Votre objet s'appelle désormais : {}", nom_affiche);
+    }
+}
+
+/// Affiche l'écran de montée de niveau et laisse le joueur répartir ses points
+/// entre la force, la vie des parties du corps, la charge maximale et les attributs.
+///
+/// # Arguments
+///
+/// * `perso_joueur` - Le personnage du joueur (modifiable).
+fn repartir_points(perso_joueur: &mut Personnage) {
+    let courbe = &*COURBE_PROGRESSION;
+    loop {
+        let progression = &perso_joueur.progression;
+        let prochain_niveau = match courbe.experience_niveau_suivant(progression.niveau) {
+            Some(experience) => format!("{} / {}", progression.experience, experience),
+            None => format!("{} (niveau maximal)", progression.experience),
+        };
+        println!("\n=== Niveau {} — Expérience : {} ===", progression.niveau, prochain_niveau);
+        println!("Points à répartir : {}", progression.points_a_repartir);
+        if progression.points_a_repartir == 0 {
+            println!("Gagnez de l'expérience en combattant et en accomplissant des quêtes pour obtenir des points.");
+            return;
+        }
+        let attributs = &perso_joueur.attributs;
+        let mut message = String::from("Améliorer :\n");
+        message.push_str(&format!("  1. Force ({} → {})\n", perso_joueur.force, perso_joueur.force.saturating_add(courbe.gain_force)));
+        message.push_str(&format!("  2. Vie (+{} par partie du corps)\n", courbe.gain_vie));
+        message.push_str(&format!("  3. Charge ({} → {})\n",
+            affichage::formater_poids(perso_joueur.poids_max()),
+            affichage::formater_poids(perso_joueur.poids_max() + courbe.gain_charge)));
+        message.push_str(&format!("  4. Agilité ({}, +{})\n", attributs.agilite, courbe.gain_attribut));
+        message.push_str(&format!("  5. Endurance ({}, +{})\n", attributs.endurance, courbe.gain_attribut));
+        message.push_str(&format!("  6. Perception ({}, +{})\n", attributs.perception, courbe.gain_attribut));
+        message.push_str(&format!("  7. Charisme ({}, +{})", attributs.charisme, courbe.gain_attribut));
+        let choix = affichage::faire_choix(
+            &message,
+            &vec!["1".to_string(), "2".to_string(), "3".to_string(), "4".to_string(), "5".to_string(), "6".to_string(), "7".to_string()]
+        );
+        let caracteristique = match choix.as_str() {
+            "1" => Caracteristique::Force,
+            "2" => Caracteristique::Vie,
+            "3" => Caracteristique::Charge,
+            "4" => Caracteristique::Agilite,
+            "5" => Caracteristique::Endurance,
+            "6" => Caracteristique::Perception,
+            "7" => Caracteristique::Charisme,
+            _ => return,
+        };
+        if let Err(e) = perso_joueur.repartir_point(caracteristique, courbe) {
+            println!("❌ {}", e);
+        }
     }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Chargement des objets
     charger_objets().expect("⚠️ Impossible de charger les objets !");
+    // Chargement des quêtes
+    charger_quetes().expect("⚠️ Impossible de charger les quêtes !");
     // Chargement des zones
     let mut zones = charger_zones().expect("⚠️ Impossible de charger les zones !");
+    // La cible de chaque clé correspond aux passages qu'elle déverrouille
+    zone::verifier_cles(&zones, &OBJETS_DISPONIBLES.read().unwrap())?;
     // Trouver l'index de la zone de départ (id == 1)
     let mut current_zone_index = zones.iter_mut().position(|zone| zone.id == 1)
         .expect("⚠️ La zone avec l'id 1 n'a pas été trouvée !");
+    // Monde joué pendant cette partie, auquel se rapporte la progression des zones
+    let mut id_monde = progression::MONDE_CLASSIQUE.to_string();
 
     let mut pnjs = PNJ::charger_pnj("src/json/pnj.json")?;
+    // Les emplois du temps ne mènent que de zone voisine en zone voisine
+    for pnj in &pnjs {
+        pnj.verifier_horaire(&zones)?;
+    }
+    // Chargement des événements aléatoires
+    let evenements = EvenementAleatoire::charger("src/json/evenement.json").expect("⚠️ Impossible de charger les événements !");
+    // Chargement des mobs
+    let mobs = Mob::charger_mob("src/json/mob.json")?;
+    // Chargement des recettes de fabrication
+    let recettes = artisanat::Recette::charger("src/json/recette.json").expect("⚠️ Impossible de charger les recettes !");
 
     let _inventaire = &mut Inventaire {
         taille: 5,
@@ -142,22 +399,42 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 return Ok(());
             }
             "1" => {
-                println!("Entrez le nom de votre personnage : ");
-                let mut nom = String::new();
-                std::io::stdin().read_line(&mut nom).expect("❌ Erreur de lecture !");
-                let nom = nom.trim();
+                let existants = Joueur::charger_joueur("src/json/personnage.json")?;
+                let nom = loop {
+                    println!("Entrez le nom de votre personnage : ");
+                    let mut nom = String::new();
+                    std::io::stdin().read_line(&mut nom).expect("❌ Erreur de lecture !");
+                    match Joueur::valider_nom(&nom, &existants) {
+                        Ok(()) => break nom.trim().to_string(),
+                        Err(e) => println!("❌ {} !", e),
+                    }
+                };
     
                 println!("Décrivez votre personnage : ");
                 let mut description = String::new();
                 std::io::stdin().read_line(&mut description).expect("❌ Erreur de lecture !");
                 let description = description.trim();
-    
-                let joueur = Joueur::creer_joueur(nom, description)?;
-                let joueur_id = joueur.personnage.id;
-                let personnages = Joueur::charger_joueur("src/json/personnage.json")?;
-                let joueur = personnages.into_iter().find(|j| j.id == joueur_id);
-                println!("Joueur créé: {:#?}", joueur);
-                _perso_joueur = joueur.expect("Aucun personnage trouvé avec cet ID.");
+
+                let classes = Classe::charger("src/json/classe.json")?;
+                let mut message = String::from("Choisissez votre classe :\n");
+                for (index, classe) in classes.iter().enumerate() {
+                    message.push_str(&format!("  {}. {} — {}\n", index + 1, classe.nom, classe.description));
+                    message.push_str(&format!("     Force {}, Agilité {}, Endurance {}, Perception {}, Charisme {}, {} pièces\n",
+                        classe.force, classe.attributs.agilite, classe.attributs.endurance,
+                        classe.attributs.perception, classe.attributs.charisme, classe.argent));
+                }
+                let choix_classe = affichage::faire_choix(
+                    &message,
+                    &(1..=classes.len()).map(|i| i.to_string()).collect()
+                );
+                let Ok(index_classe) = choix_classe.parse::<usize>() else { continue };
+                let classe = &classes[index_classe - 1];
+
+                let joueur = Joueur::creer_joueur(&nom, description, classe)?;
+                println!("✅ {} ({}) est prêt pour l'aventure !", nom, classe.nom);
+                _perso_joueur = joueur.personnage;
+                // Répartition des points de départ
+                repartir_points(&mut _perso_joueur);
                 break;
             }
             "2" => {
@@ -199,8 +476,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "admin" => {
                 loop {
                     let choix_type = affichage::faire_choix(
-                        "Choisissez le type de personnage à créer (1 PNJ, 2 Mob, 3 Retour) : ",
-                        &vec!["1".to_string(), "2".to_string(), "3".to_string()]
+                        "Choisissez le type de personnage à créer (1 PNJ, 2 Mob), 3 pour exporter le graphe des zones, 4 pour générer un monde, 5 Retour : ",
+                        &vec!["1".to_string(), "2".to_string(), "3".to_string(), "4".to_string(), "5".to_string()]
                     );
     
                     match choix_type.as_str() {
@@ -252,6 +529,71 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             }
                         }
                         "3" => {
+                            match charger_zones() {
+                                Ok(zones_fichier) => {
+                                    match export::exporter(&zones_fichier, "monde") {
+                                        Ok(()) => println!("✅ Graphe exporté dans monde.dot et monde.json"),
+                                        Err(e) => println!("❌ Erreur lors de l'export : {}", e),
+                                    }
+                                    for anomalie in export::anomalies(&zones_fichier) {
+                                        println!("⚠️ {}", anomalie.description());
+                                    }
+                                }
+                                Err(e) => println!("❌ Impossible de charger les zones : {}", e),
+                            }
+                        }
+                        "4" => {
+                            let modeles = match generation::Modeles::charger("src/json/generation.json") {
+                                Ok(modeles) => modeles,
+                                Err(e) => {
+                                    println!("❌ Impossible de charger les modèles de génération : {}", e);
+                                    continue;
+                                }
+                            };
+                            println!("Entrez la graine du monde (vide pour une graine au hasard) : ");
+                            let mut graine = String::new();
+                            std::io::stdin().read_line(&mut graine).expect("❌ Erreur de lecture !");
+                            let graine: u64 = graine.trim().parse().unwrap_or_else(|_| rand::rng().random());
+                            println!("Entrez le nombre de zones (1 à 255, 10 par défaut) : ");
+                            let mut nombre_zones = String::new();
+                            std::io::stdin().read_line(&mut nombre_zones).expect("❌ Erreur de lecture !");
+                            let parametres = generation::ParametresGeneration {
+                                graine,
+                                nombre_zones: nombre_zones.trim().parse().unwrap_or(10),
+                                ..Default::default()
+                            };
+                            let pnj_ids: Vec<u32> = pnjs.iter().map(|p| p.personnage.id).collect();
+                            let monde = generation::generer(&parametres, &modeles, &pnj_ids);
+                            let (chemin_zones, chemin_coffres) = ("monde_genere_zone.json", "monde_genere_coffre.json");
+                            if let Err(e) = moteur::ecrire_zones(&monde.zones, chemin_zones, chemin_coffres) {
+                                println!("❌ Erreur lors de l'écriture du monde : {}", e);
+                                continue;
+                            }
+                            println!("✅ Monde de {} zones (graine {}) écrit dans {} et {}", monde.zones.len(), graine, chemin_zones, chemin_coffres);
+                            let jouer = affichage::faire_choix(
+                                "Jouer dans ce monde pour cette partie ? (oui/non)",
+                                &vec!["oui".to_string(), "non".to_string()]
+                            );
+                            if jouer == "oui" {
+                                match moteur::charger_zones_depuis(chemin_zones, chemin_coffres) {
+                                    Ok(zones_generees) => {
+                                        zones = zones_generees;
+                                        current_zone_index = zones.iter().position(|z| z.id == 1).unwrap_or(0);
+                                        id_monde = format!("genere_{}_{}", graine, parametres.nombre_zones);
+                                        // Les emplois du temps des PNJs ne valent que pour le monde classique
+                                        for (pnj_id, zone_id) in &monde.placements_pnjs {
+                                            if let Some(pnj) = pnjs.iter_mut().find(|p| p.personnage.id == *pnj_id) {
+                                                pnj.zone_id = *zone_id;
+                                                pnj.deplacement = personnage::Deplacement::Fixe;
+                                            }
+                                        }
+                                        println!("🌍 Le monde généré sera utilisé pour cette partie.");
+                                    }
+                                    Err(e) => println!("❌ Impossible de charger le monde généré : {}", e),
+                                }
+                            }
+                        }
+                        "5" => {
                             println!("🔙 Retour au menu principal.");
                             break;
                         }
@@ -268,10 +610,50 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Message d'accueil
     // affichage::notifier(&zones[current_zone_index], "✨ Bienvenue dans le RustRPG !", &pnjs);
     
+    // Le joueur reprend dans la zone où il s'était arrêté, dans le monde joué
+    _perso_joueur.progression.changer_monde(&id_monde);
+    if let Some(index) = zones.iter().position(|z| z.id == _perso_joueur.progression.zone_actuelle) {
+        current_zone_index = index;
+    }
+    // Les PNJs reprennent l'état où ce joueur les a laissés ; ceux qu'il a tués restent morts
+    PNJ::restaurer_etats(&mut pnjs, &_perso_joueur);
+    PNJ::appliquer_morts(&mut pnjs, &_perso_joueur);
+    // Les zones achetées par ce joueur, ou ouvertes par ses quêtes et dialogues, restent ouvertes
+    for zone in zones.iter_mut() {
+        if _perso_joueur.progression.a_acces_zone(zone.id) {
+            zone.ouvert = true;
+        }
+    }
     affichage::afficher_zone(&zones[current_zone_index], &pnjs);
-    let mut rng = rand::rng();
     // Boucle principale du jeu
     loop {
+        // Les PNJs se déplacent selon leur emploi du temps ou au hasard
+        let zone_joueur = zones[current_zone_index].id as u32;
+        for (index, zone_quittee) in PNJ::deplacer_pnjs(&mut pnjs, &zones, personnage::heure_jeu(Utc::now())) {
+            if zone_quittee == zone_joueur {
+                let msg = format!("🚶 {} quitte la zone.", pnjs[index].personnage.nom);
+                affichage::notifier(&zones[current_zone_index], &msg, &pnjs);
+            } else if pnjs[index].zone_id == zone_joueur {
+                let msg = format!("🚶 {} arrive dans la zone.", pnjs[index].personnage.nom);
+                affichage::notifier(&zones[current_zone_index], &msg, &pnjs);
+            }
+        }
+        // Les mobs réapparaissent et les PNJs hostiles de la zone passent à l'attaque
+        let maintenant = Utc::now();
+        for zone in zones.iter_mut() {
+            zone.faire_reapparaitre(&mobs, maintenant);
+        }
+        let revenus = zone::percevoir_revenus(&zones, &mut _perso_joueur, maintenant);
+        if revenus > 0 {
+            let msg = format!("💰 Vos zones vous ont rapporté {} pièces.", revenus);
+            affichage::notifier(&zones[current_zone_index], &msg, &pnjs);
+        }
+        PNJ::affronter_hostiles(&mut pnjs, &mut _perso_joueur, &mut zones, current_zone_index);
+        if !_perso_joueur.est_vivant {
+            affichage::notifier(&zones[current_zone_index], "Vous êtes mort... La partie est terminée !", &pnjs);
+            break Ok(());
+        }
+        _perso_joueur.progression.decouvrir_zone(zones[current_zone_index].id, &zones[current_zone_index].destinations());
         let nbr_coffres = zones[current_zone_index].compter_coffre();
         let tableau: Vec<usize>;
         let mut options = vec![
@@ -281,18 +663,28 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "i".to_string(), // autre option
             "t".to_string(), // autre option
             "s".to_string(), // afficher l'état de santé
+            "j".to_string(), // journal de quêtes
+            "n".to_string(), // répartir les points de niveau
+            "m".to_string(), // afficher la carte
+            "v".to_string(), // voyager vers une zone visitée
+            "f".to_string(), // fabriquer un objet
         ];
 
 
         let pnjs_in_zone: Vec<usize> = pnjs.iter()
             .enumerate()
-            .filter(|(_, p)| p.zone_id == zones[current_zone_index].id as u32)
+            .filter(|(_, p)| p.zone_id == zones[current_zone_index].id as u32 && p.personnage.est_vivant)
             .map(|(i, _)| i)
             .collect();
 
         if !pnjs_in_zone.is_empty() {
             options.push("p".to_string()); // interagir avec les PNJ
         }
+        let zone_possedee = _perso_joueur.progression.possede_zone(zones[current_zone_index].id);
+        if zone_possedee {
+            options.push("r".to_string()); // se reposer
+            options.push("e".to_string()); // ouvrir la réserve
+        }
 
         for i in 1..=nbr_coffres {
             options.push(i.to_string());
@@ -305,7 +697,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         message_commandes.push_str("  c : Fouiller la zone (coffres)\n");
         message_commandes.push_str("  t : Fouiller le sol de la zone (objets au sol)\n");
         message_commandes.push_str("  p : Parler/interagir avec les PNJ (si présents)\n");
+        if zone_possedee {
+            message_commandes.push_str("  r : Se reposer gratuitement dans votre zone\n");
+            message_commandes.push_str("  e : Ouvrir votre réserve\n");
+        }
         message_commandes.push_str("  s : Voir l'état de santé du joueur, son équipement et son argent\n");
+        message_commandes.push_str("  j : Ouvrir le journal de quêtes\n");
+        message_commandes.push_str("  m : Afficher la carte du monde\n");
+        message_commandes.push_str("  v : Voyager vers une zone déjà visitée\n");
+        message_commandes.push_str("  f : Fabriquer un objet à partir d'une recette\n");
+        message_commandes.push_str(&format!("  n : Niveau et points à répartir ({})\n", _perso_joueur.progression.points_a_repartir));
         if nbr_coffres > 0 {
             for i in 1..=nbr_coffres {
                 message_commandes.push_str(&format!("  {} : Ouvrir le coffre {}\n", i, i));
@@ -321,16 +722,48 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         );
         match choix.as_str() {
             "q" => {
-                  affichage::notifier(&zones[current_zone_index], "👋 Au revoir !", &pnjs);
+                  _perso_joueur.progression.zone_actuelle = zones[current_zone_index].id;
+                  PNJ::memoriser_etats(&pnjs, &mut _perso_joueur);
+                  match _perso_joueur.mettre_a_jour_json("src/json/personnage.json") {
+                      Ok(()) => affichage::notifier(&zones[current_zone_index], "💾 Partie sauvegardée. 👋 Au revoir !", &pnjs),
+                      Err(e) => affichage::notifier(&zones[current_zone_index], &format!("❌ Échec de la sauvegarde : {}", e), &pnjs),
+                  }
                   break Ok(());
               }
+            "j" => {
+                quete::afficher_journal(&_perso_joueur, &zones, &mobs);
+            }
+            "n" => repartir_points(&mut _perso_joueur),
+            "v" => {
+                let destinations: Vec<&Zone> = zones.iter()
+                    .filter(|z| z.id != zones[current_zone_index].id && _perso_joueur.progression.a_visite(z.id))
+                    .collect();
+                if destinations.is_empty() {
+                    affichage::notifier(&zones[current_zone_index], "Vous ne connaissez encore aucune autre zone.", &pnjs);
+                    continue;
+                }
+                let mut message = String::from("🧭 Vers quelle zone voulez-vous voyager ?\n");
+                for zone in &destinations {
+                    message.push_str(&format!("  {} : {}\n", zone.id, zone.nom));
+                }
+                let choix_zone = affichage::faire_choix(
+                    &message,
+                    &destinations.iter().map(|z| z.id.to_string()).collect()
+                );
+                if let Ok(destination) = choix_zone.parse::<u8>() {
+                    if !voyager(&mut zones, &mut current_zone_index, destination, &mut _perso_joueur, &mut pnjs, &mobs, &evenements) {
+                        let msg = "Vous êtes mort... La partie est terminée !".to_string();
+                        affichage::notifier(&zones[current_zone_index], &msg, &pnjs);
+                        return Ok(());
+                    }
+                }
+            }
+            "m" => println!("{}", carte::dessiner_carte(&zones, current_zone_index, &_perso_joueur, &pnjs)),
             "p" => {
                 if !pnjs_in_zone.is_empty() {
                     println!("Choisissez un PNJ pour interagir :");
                     for (index, &pnj_index) in pnjs_in_zone.iter().enumerate() {
-                        if pnjs[pnj_index].personnage.est_vivant {
-                            println!("{}. {}", index + 1, pnjs[pnj_index].personnage.nom);
-                        }
+                        println!("{}. {}", index + 1, pnjs[pnj_index].personnage.nom);
                     }
 
                     let mut choix_pnj = String::new();
@@ -338,7 +771,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     if let Ok(index) = choix_pnj.trim().parse::<usize>() {
                         if index > 0 && index <= pnjs_in_zone.len() {
                             let pnj_index = pnjs_in_zone[index - 1];
-                            pnjs[pnj_index].interagir(&mut _perso_joueur, &mut zones, current_zone_index);
+                            let resultat = pnjs[pnj_index].interagir(&mut _perso_joueur, &mut zones, current_zone_index);
+                            PNJ::reagir_violence(&mut pnjs, pnj_index, &resultat);
                         } else {
                             println!("Numéro de PNJ invalide !");
                         }
@@ -348,166 +782,143 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
             "i" => {
-                println!("Votre inventaire : ");
-                match _perso_joueur.inventaire.afficher(true, &zones[current_zone_index], &pnjs){
-                    Some(obj)=> {
-                        let choix_utiliser = affichage::faire_choix(
-                            "Voulez vous utiliser l'objet ? (oui ou non)",
-                            &vec!["oui".to_string(), "non".to_string()]
-                        );
-
-                        match choix_utiliser.as_str() {
-                            "oui" => {
-                                let id = _perso_joueur.inventaire.objets[obj].objet_id;
-                                if let Some(o) = OBJETS_DISPONIBLES.read().unwrap().get(&(id as u8)) {
-                                    println!("{}", o);
-                                    if o.est_equipement() {
-                                        if o.est_pour_emplacement(Emplacement::Tete) {
-                                            tableau = vec![0]
-                                        }
-                                        else {
-                                            tableau = vec![1]
-                                        }
+                println!("Votre inventaire (poids : {} / {}) : ",
+                    affichage::formater_poids(_perso_joueur.poids_porte()),
+                    affichage::formater_poids(_perso_joueur.poids_max()));
+                if let Some(obj) = _perso_joueur.inventaire.afficher(true, &zones[current_zone_index], &pnjs) {
+                    let choix_utiliser = affichage::faire_choix(
+                        "Voulez vous utiliser l'objet ? (oui, non ou renommer)",
+                        &vec!["oui".to_string(), "non".to_string(), "renommer".to_string()]
+                    );
 
-                                        for i in tableau{
-                                            if _perso_joueur.parties_du_corps[i].equipement().objets.is_empty() {
-                                                let objet : ObjetInventaire = _perso_joueur.inventaire.récupérer_objet_2(obj);
-                                                _perso_joueur.parties_du_corps[i].ajouter_equipement(objet.objet_id);
-                                                println!("Equipement équipé !");
-                                            }
-                                            else {
-                                                let new_choix = affichage::faire_choix(
-                                                    "Equipement plein, voulez vous inverser l'objet ? (oui ou non)",
-                                                    &vec!["oui".to_string(), "non".to_string()]
-                                                );
-                                                match new_choix.as_str() {
-                                                    "oui" => {
-                                                        let objet : ObjetInventaire = _perso_joueur.inventaire.récupérer_objet_2(obj);
-                                                        let objet2 : ObjetInventaire = _perso_joueur.parties_du_corps[i].récupérer_objet(obj);
-                                                        _perso_joueur.parties_du_corps[i].ajouter_equipement(objet.objet_id);
-                                                        _perso_joueur.inventaire.ajouter_objet(objet2.objet_id);
-                                                    }
-
-                                                    _ => {
-
-                                                    }
-                                                }
-                                            }
-                                        }
+                    match choix_utiliser.as_str() {
+                        "oui" => {
+                            let id = _perso_joueur.inventaire.objets[obj].objet_id;
+                            if let Some(o) = OBJETS_DISPONIBLES.read().unwrap().get(&id) {
+                                println!("{}", o);
+                                if let Some(instance) = &_perso_joueur.inventaire.objets[obj].instance {
+                                    println!("- Exemplaire : {}", instance);
+                                }
+                                if o.est_equipement() {
+                                    if o.est_pour_emplacement(Emplacement::Tete) {
+                                        tableau = vec![0]
+                                    }
+                                    else {
+                                        tableau = vec![1]
                                     }
-                                    else if o.est_arme() {
-                                        let choix = affichage::faire_choix(
-                                            "Dans quelle main equipée l'objet ? (g ou d ou q)",
-                                            &vec!["g".to_string(), "d".to_string()]
-                                        );
-                                        match choix.as_str() {
-                                            "g" => {
-                                                if _perso_joueur.parties_du_corps[3].equipement().objets.is_empty() {
-                                                    let objet : ObjetInventaire = _perso_joueur.inventaire.récupérer_objet_2(obj);
-                                                    _perso_joueur.parties_du_corps[3].ajouter_equipement(objet.objet_id);
-                                                    println!("Equipement équipé !");
-                                                }
-                                                else {
-                                                    let new_choix = affichage::faire_choix(
-                                                        "Equipement plein, voulez vous inverser l'objet ? (oui ou non)",
-                                                        &vec!["oui".to_string(), "non".to_string()]
-                                                    );
-                                                    match new_choix.as_str() {
-                                                        "oui" => {
-                                                            let objet : ObjetInventaire = _perso_joueur.inventaire.récupérer_objet_2(obj);
-                                                            let objet2 : ObjetInventaire = _perso_joueur.parties_du_corps[3].récupérer_objet(obj);
-                                                            _perso_joueur.parties_du_corps[3].ajouter_equipement(objet.objet_id);
-                                                            _perso_joueur.inventaire.ajouter_objet(objet2.objet_id);
-                                                        }
-
-                                                        _ => {
-
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            "d" => {
-                                                if _perso_joueur.parties_du_corps[2].equipement().objets.is_empty() {
-                                                    let objet : ObjetInventaire = _perso_joueur.inventaire.récupérer_objet_2(obj);
-                                                    _perso_joueur.parties_du_corps[2].ajouter_equipement(objet.objet_id);
-                                                    println!("Equipement équipé !");
-                                                }
-                                                else {
-                                                    let new_choix = affichage::faire_choix(
-                                                        "Equipement plein, voulez vous inverser l'objet ? (oui ou non)",
-                                                        &vec!["oui".to_string(), "non".to_string()]
-                                                    );
-                                                    match new_choix.as_str() {
-                                                        "oui" => {
-                                                            let objet : ObjetInventaire = _perso_joueur.inventaire.récupérer_objet_2(obj);
-                                                            let objet2 : ObjetInventaire = _perso_joueur.parties_du_corps[2].récupérer_objet(obj);
-                                                            _perso_joueur.parties_du_corps[2].ajouter_equipement(objet.objet_id);
-                                                            _perso_joueur.inventaire.ajouter_objet(objet2.objet_id);
-                                                        }
-
-                                                        _ => {
-
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            _ => {
 
-                                            }
-                                        }
+                                    for i in tableau{
+                                        equiper_objet(&mut _perso_joueur, obj, i, &mut zones[current_zone_index]);
                                     }
-                                    else if o.est_soin() {
-                                        let choix = affichage::faire_choix(
-                                            "Sur quelle partie du corps utilisé l'objet ? (0 : tete, 1 : torse, 2 : bras droit, 3 : bras gauche, 4 : jambre droite, 5 : jambe gauche, q : quitter)",
-                                            &vec!["0".to_string(), "1".to_string(), "2".to_string(), "3".to_string(), "4".to_string(), "5".to_string(), "q".to_string()]
-                                        );
-                                        match choix.as_str() {
-                                            "0" => println!("Soin de la tête"),
-                                            "1" => println!("Soin du torse"),
-                                            "2" => println!("Soin du bras droit"),
-                                            "3" => println!("Soin du bras gauche"),
-                                            "4" => println!("Soin de la jambe droite"),
-                                            "5" => println!("Soin de la jambe gauche"),
-                                            _ => println!("Annulation du soin.")
-                                        }
                                 }
+                                else if o.est_arme() {
+                                    let choix = affichage::faire_choix(
+                                        "Dans quelle main equipée l'objet ? (g ou d ou q)",
+                                        &vec!["g".to_string(), "d".to_string()]
+                                    );
+                                    match choix.as_str() {
+                                        "g" => equiper_objet(&mut _perso_joueur, obj, 3, &mut zones[current_zone_index]),
+                                        "d" => equiper_objet(&mut _perso_joueur, obj, 2, &mut zones[current_zone_index]),
+                                        _ => {
+
+                                        }
+                                    }
                                 }
-                                else{
-                                    println!("pas d'objet trouvé");
+                                else if o.est_soin() {
+                                    let choix = affichage::faire_choix(
+                                        "Sur quelle partie du corps utilisé l'objet ? (0 : tete, 1 : torse, 2 : bras droit, 3 : bras gauche, 4 : jambre droite, 5 : jambe gauche, q : quitter)",
+                                        &vec!["0".to_string(), "1".to_string(), "2".to_string(), "3".to_string(), "4".to_string(), "5".to_string(), "q".to_string()]
+                                    );
+                                    match choix.as_str() {
+                                        "0" => println!("Soin de la tête"),
+                                        "1" => println!("Soin du torse"),
+                                        "2" => println!("Soin du bras droit"),
+                                        "3" => println!("Soin du bras gauche"),
+                                        "4" => println!("Soin de la jambe droite"),
+                                        "5" => println!("Soin de la jambe gauche"),
+                                        _ => println!("Annulation du soin.")
+                                    }
+                            }
+                                else {
+                                    match &o.objet_type {
+                                        TypeObjet::Nourriture { vie } => {
+                                            _perso_joueur.inventaire.récupérer_objet_2(obj);
+                                            let restaures = _perso_joueur.restaurer_vie(*vie);
+                                            println!("Vous mangez {} et récupérez {} points de vie.", o.nom, restaures);
+                                        }
+                                        TypeObjet::Parchemin { experience } => {
+                                            _perso_joueur.inventaire.récupérer_objet_2(obj);
+                                            println!("Vous lisez {}.", o.nom);
+                                            _perso_joueur.gagner_experience(*experience);
+                                        }
+                                        TypeObjet::Cle { cible: Some(zone_id) } => {
+                                            let nom = zones.iter().find(|z| z.id == *zone_id)
+                                                .map(|z| z.nom_pour(&_perso_joueur))
+                                                .unwrap_or_else(|| "inconnue".to_string());
+                                            println!("Cette clé ouvre le passage vers la zone {}.", nom);
+                                        }
+                                        TypeObjet::Cle { cible: None } => println!("Cette clé s'utilise en ouvrant un coffre fermé."),
+                                        TypeObjet::Outil => println!("Cet outil sert automatiquement quand vous en avez besoin."),
+                                        TypeObjet::Projectile { .. } => println!("Cet objet se lance sur un adversaire au début d'un combat."),
+                                        TypeObjet::Quete => println!("Cet objet est destiné à une quête, gardez-le précieusement."),
+                                        TypeObjet::Materiau => println!("Ce matériau sert à fabriquer des objets (commande f)."),
+                                        _ => println!("Cet objet ne peut pas être utilisé."),
+                                    }
                                 }
-
                             }
-                            _ => {
-                                //println!("Vous vous débarassez de l'objet");
-                                let choix_jeter = affichage::faire_choix(
-                                    "Voulez vous jeter l'objet ? (oui ou non)",
-                                    &vec!["oui".to_string(), "non".to_string()]
-                                );
-
-                                match choix_jeter.as_str() {
-                                    "oui" => {
-                                        let objet : ObjetInventaire = _perso_joueur.inventaire.récupérer_objet_2(obj);
-                                        zones[current_zone_index].objet_zone.ajouter_objet(objet.objet_id);
+                            else{
+                                println!("pas d'objet trouvé");
+                            }
+
+                        }
+                        "renommer" => renommer_objet(&mut _perso_joueur, obj),
+                        _ => {
+                            //println!("Vous vous débarassez de l'objet");
+                            let choix_jeter = affichage::faire_choix(
+                                "Voulez vous jeter l'objet ? (oui ou non)",
+                                &vec!["oui".to_string(), "non".to_string()]
+                            );
+
+                            match choix_jeter.as_str() {
+                                "oui" => {
+                                    let id_objet = _perso_joueur.inventaire.objets[obj].objet_id;
+                                    let echangeable = OBJETS_DISPONIBLES.read().unwrap().get(&id_objet).is_none_or(|o| o.est_echangeable());
+                                    if !echangeable {
+                                        println!("Un objet de quête ne peut pas être jeté.")
+                                    } else if zones[current_zone_index].objet_zone.ajouter_exemplaire(ObjetInventaire { nombre: 1, .._perso_joueur.inventaire.objets[obj].clone() }).is_ok() {
+                                        _perso_joueur.inventaire.retirer_exemplaires(obj, 1);
                                         println!("Vous vous débarassez de l'objet")
+                                    } else {
+                                        println!("Il n'y a plus de place au sol pour jeter cet objet.")
                                     }
-                                    _ => {
-                                        println!("Vous ne faites rien de cette objet.")
-                                    }
+                                }
+                                _ => {
+                                    println!("Vous ne faites rien de cette objet.")
                                 }
                             }
                         }
                     }
-                    None => ()
                 }
             }
+            "f" => artisanat::menu_artisanat(&recettes, &mut _perso_joueur, &zones, current_zone_index, &pnjs),
+            "e" => reserve::ouvrir_reserve(&mut _perso_joueur, &zones[current_zone_index], &pnjs),
+            "r" => {
+                affichage::notifier(&zones[current_zone_index], "😴 Vous vous reposez chez vous...", &pnjs);
+                sleep(_perso_joueur.duree_deplacement());
+                let msg = match _perso_joueur.se_reposer() {
+                    0 => "Vous êtes reposé, mais vous n'aviez aucune blessure à soigner.".to_string(),
+                    n => format!("Vous êtes reposé : {} partie(s) du corps soignée(s).", n),
+                };
+                affichage::notifier(&zones[current_zone_index], &msg, &pnjs);
+            }
             "c" => {
                 affichage::notifier(&zones[current_zone_index], "Fouillage de la zone en cours...", &pnjs);
-                sleep(Duration::from_secs(5));
-                zones[current_zone_index].fouiller_zone();
+                sleep(_perso_joueur.duree_fouille());
+                zones[current_zone_index].fouiller_zone(&_perso_joueur);
                 affichage::afficher_zone(&zones[current_zone_index], &pnjs);
             }
             "t" => {
-                let msg = format!("Fouillage de la zone en cours...");
+                let msg = "Fouillage de la zone en cours...".to_string();
                 affichage::notifier(&zones[current_zone_index], &msg, &pnjs);
                 sleep(Duration::from_secs(5));
                 let zone_clone = zones[current_zone_index].clone();
@@ -516,7 +927,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 if let Some(()) = objet_zone.afficher_inventaire_zone_et_coffre(&zone_clone, &mut _perso_joueur, &pnjs)
                 {
                 } else {
-                    let msg = format!("Vous laissez l'objet par terre ...");
+                    let msg = "Vous laissez l'objet par terre ...".to_string();
                     affichage::notifier(&zones[current_zone_index], &msg, &pnjs);
                 }
             }
@@ -531,62 +942,24 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     "🚪 Vers quelle direction voulez-vous aller ?",
                     &directions_disponibles
                 );
+                let zone_depart = current_zone_index;
                 se_deplacer(&mut zones, &mut current_zone_index, &direction, &mut _perso_joueur, &pnjs);
-                if zones[current_zone_index].mob_present {
-                    let mut rng = rand::rng();
-                    let chance: f32 = rng.random();
-
-                    if chance < 0.9 {
-                        let mobs = Mob::charger_mob("src/json/mob.json")?;
-
-                        let mut rng = rand::rng();
-                        if let Some(mob_choisi) = mobs.choose(&mut rng) {
-                            println!(
-                                "Mob choisi au hasard : ID: {}, Nom: {}, Description: {}",
-                                mob_choisi.id, mob_choisi.nom, mob_choisi.description
-                            );
-                            let resultat = combattre(
-                                _perso_joueur.clone(),
-                                mob_choisi.clone(),
-                                &zones[current_zone_index],
-                                &pnjs
-                            );
-                            if resultat.etat_final_joueur.est_vivant {
-                                _perso_joueur.parties_du_corps = resultat.etat_final_joueur.parties_du_corps;
-                                for p in &_perso_joueur.parties_du_corps{
-                                    if !p.est_saine() {
-                                        let msg = format!("Votre {} est blessé", p.nom());
-                                        affichage::notifier(&zones[current_zone_index], &msg, &pnjs)
-                                    }
-                                }
-                                affichage::notifier(&zones[current_zone_index], "Vous avez gagné le combat !", &pnjs);
-                                _perso_joueur.ajouter_argent(mob_choisi.argent);
-                                let msg = format!("Vous ramassez {} pièces d'or sur le mob !", mob_choisi.argent);
-                                affichage::notifier(&zones[current_zone_index], &msg, &pnjs)
-                            }
-                            else { 
-                                let msg = format!("Malheureusement vous venez de perdre la partie s'arrete pour vous ... N'hésitez pas a refaire une partie");
-                                affichage::notifier(&zones[current_zone_index], &msg, &pnjs);
-                                return Ok(());
-                            }
-                        }
-
-                    }else{
-                        let msg = format!("Vous êtes chanceux le mob ne vous attaque pas.");
-                        affichage::notifier(&zones[current_zone_index], &msg, &pnjs);
-                    }
-                }
-                
-            }
-            "nord" | "sud" | "est" | "ouest" => {
-                se_deplacer(&mut zones, &mut current_zone_index, &choix, &mut _perso_joueur, &pnjs);
-                if rng.random_range(0..99) < 10 {
-                    affichage::notifier(&zones[current_zone_index], "🎉 L'événement rare s'est produit !", &pnjs);
+                if current_zone_index != zone_depart
+                    && arriver_dans_zone(&mut zones, zone_depart, current_zone_index, &mut _perso_joueur, &mut pnjs, &mobs, &evenements) == IssueArrivee::Mort {
+                    return Ok(());
                 }
             }
             "s" => {
                 println!("\n=== État de santé de {} ===", _perso_joueur.nom);
+                println!("Niveau : {} ({} XP) - Force : {}", _perso_joueur.progression.niveau, _perso_joueur.progression.experience, _perso_joueur.force);
+                let attributs = &_perso_joueur.attributs;
+                println!("Agilité : {} - Endurance : {} - Perception : {} - Charisme : {}",
+                    attributs.agilite, attributs.endurance, attributs.perception, attributs.charisme);
                 println!("Argent : {} pièces", _perso_joueur.argent);
+                println!("Poids porté : {} / {}{}",
+                    affichage::formater_poids(_perso_joueur.poids_porte()),
+                    affichage::formater_poids(_perso_joueur.poids_max()),
+                    if _perso_joueur.est_surcharge() { " (surchargé)" } else { "" });
                 println!("Statut: {}", if _perso_joueur.est_vivant { "Vivant" } else { "Mort" });
                 let now = Utc::now();
                 for partie in &_perso_joueur.parties_du_corps {
@@ -629,6 +1002,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         if coffre.ouvrir(&zone_clone, &mut _perso_joueur , &pnjs).is_none() {
                             continue;
                         }
+                        quete::signaler(&mut _perso_joueur, &Evenement::CoffreOuvert { zone_id: zone_clone.id, coffre_id: coffre._id });
 
                         if let Some(()) = coffre
                             .inventaire
@@ -649,7 +1023,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             },
         }
         if !_perso_joueur.est_vivant {
-            let msg = format!("Vous êtes mort... La partie est terminée !");
+            let msg = "Vous êtes mort... La partie est terminée !".to_string();
             affichage::notifier(&zones[current_zone_index], &msg, &pnjs);
             break Ok(());
         }
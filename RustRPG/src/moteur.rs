@@ -4,13 +4,14 @@ use std::fs;
 use std::error::Error;
 use rand::Rng;
 use rand::prelude::IndexedRandom;
-use serde_json::Value;
+use serde_json::{json, Value};
 use coffre::Coffre;
 use crate::{coffre, inventaire, zone};
 use zone::Zone;
 use zone::Connexion;
-use inventaire::{Inventaire};
+use inventaire::{Instance, Inventaire, ObjetInventaire};
 use crate::objet::{ajouter_objet, TypeObjet, OBJETS_DISPONIBLES};
+use crate::quete::{ajouter_quete, Quete};
 
 /// Structure temporaire représentant une zone chargée depuis un JSON.
 ///
@@ -78,6 +79,26 @@ struct CoffreTemporaire {
     _visible: String,
     /// Inventaire du coffre (liste d'inventaires, JSON utilise un tableau).
     _inventaire: Vec<InventaireTemporaire>, // Le JSON utilise un tableau
+    /// Difficulté à trouver le coffre s'il est caché.
+    #[serde(default)]
+    difficulte: u8,
+    /// Identifiant de l'objet nécessaire pour trouver le coffre.
+    #[serde(default)]
+    outil: Option<u8>,
+    /// Difficulté de la serrure.
+    #[serde(default)]
+    serrure: u8,
+    /// Identifiant de la clé qui ouvre le coffre.
+    #[serde(default = "cle_par_defaut")]
+    cle: u8,
+    /// Dégâts infligés par le piège du coffre.
+    #[serde(default)]
+    piege: u32,
+}
+
+/// Clé utilisée par les coffres qui n'en déclarent pas.
+fn cle_par_defaut() -> u8 {
+    coffre::ID_CLE
 }
 
 
@@ -104,8 +125,23 @@ pub fn charger_json(chemin: &str)->Result<String, Box<dyn Error>>{
 ///
 /// Retourne une erreur si le chargement ou la conversion échoue.
 pub fn charger_zones() -> Result<Vec<Zone>, Box<dyn Error>> {
-    let coffres_totaux: HashMap<u8, Vec<Coffre>> = charger_coffres().expect("⚠️ Impossible de charger les coffres !");
-    let contenu = charger_json("src/json/zone.json")?;
+    charger_zones_depuis("src/json/zone.json", "src/json/coffre.json")
+}
+
+/// Charge les zones et leurs coffres depuis une paire de fichiers au format de
+/// `zone.json` et `coffre.json`.
+///
+/// # Arguments
+///
+/// * `chemin_zones` - Chemin du fichier des zones.
+/// * `chemin_coffres` - Chemin du fichier des coffres.
+///
+/// # Erreurs
+///
+/// Retourne une erreur si le chargement ou la conversion échoue.
+pub fn charger_zones_depuis(chemin_zones: &str, chemin_coffres: &str) -> Result<Vec<Zone>, Box<dyn Error>> {
+    let coffres_totaux: HashMap<u8, Vec<Coffre>> = charger_coffres(chemin_coffres)?;
+    let contenu = charger_json(chemin_zones)?;
     let zones_temp: Vec<ZoneTemporaire> = serde_json::from_str::<Vec<Value>>(&contenu)?
     .into_iter()
     .filter(|zone| zone["type"] == "zone")
@@ -118,7 +154,7 @@ pub fn charger_zones() -> Result<Vec<Zone>, Box<dyn Error>> {
     }
 
     let mut zones_finales = Vec::new();
-    for (_, zone_temp) in &map_temp {
+    for zone_temp in map_temp.values() {
         let id_numerique = zone_temp.id_texte.parse::<u8>()?;
         let coffre_zone: Vec<Coffre> = coffres_totaux.get(&id_numerique).cloned().unwrap_or_else(Vec::new);
         let mut ouvert = true;
@@ -134,13 +170,17 @@ pub fn charger_zones() -> Result<Vec<Zone>, Box<dyn Error>> {
         let zone_finale = Zone {
             id: id_numerique,
             nom: zone_temp.nom.clone(),
-            ouvert: ouvert,
+            ouvert,
             description: zone_temp.description.clone(),
             connection: zone_temp.connection.clone(),
             coffres: coffre_zone,
             objet_zone : inventaire,
             mob_present: zone_temp.mob_present,
+            mob: None,
+            reapparition: None,
             prix,
+            tentatives_fouille: 0,
+            meteo: None,
         };
         zones_finales.push(zone_finale);
     }
@@ -149,13 +189,18 @@ pub fn charger_zones() -> Result<Vec<Zone>, Box<dyn Error>> {
 }
 
 
-/// Charge et retourne un dictionnaire des coffres par zone.
+/// Charge et retourne un dictionnaire des coffres par zone depuis un fichier au
+/// format de `coffre.json`.
+///
+/// # Arguments
+///
+/// * `chemin` - Chemin du fichier des coffres.
 ///
 /// # Erreurs
 ///
 /// Retourne une erreur si le chargement ou la conversion échoue.
-pub fn charger_coffres() -> Result<HashMap<u8, Vec<Coffre>>, Box<dyn Error>> {
-    let contenu = charger_json("src/json/coffre.json")?;
+pub fn charger_coffres(chemin: &str) -> Result<HashMap<u8, Vec<Coffre>>, Box<dyn Error>> {
+    let contenu = charger_json(chemin)?;
     let coffres_temp: Vec<CoffreTemporaire> = serde_json::from_str(&contenu)?;
     let mut coffre_finales: HashMap<u8, Vec<Coffre>> = HashMap::new();
 
@@ -170,18 +215,14 @@ pub fn charger_coffres() -> Result<HashMap<u8, Vec<Coffre>>, Box<dyn Error>> {
         if coffre._visible == "false" {
             visible = false;
         }
-        // Récupérer le premier élément de la liste `inventaire`
-        // let inventaire_temp = coffre.inventaire.get(0).ok_or("Inventaire vide")?;
-        //
-        // let inventaire = Inventaire {
-        //     taille: inventaire_temp.taille_texte.parse::<u8>()?, // Conversion de taille
-        //     objets: inventaire_temp.objets.iter().map(|o| ObjetInventaire {
-        //         nombre: o.nombre,
-        //         objet_id: o.objet_id,
-        //     }).collect(), // Mapper les objets avec leurs quantités
-        // };
+        // La capacité du coffre est lue dans le premier élément de la liste `inventaire`
+        let taille = coffre._inventaire
+            .first()
+            .map(|inv| inv._taille_texte.parse::<u8>())
+            .transpose()?
+            .unwrap_or(10);
         let inventaire = Inventaire {
-            taille: 10,
+            taille,
             objets: Vec::new(),
         };
 
@@ -190,11 +231,16 @@ pub fn charger_coffres() -> Result<HashMap<u8, Vec<Coffre>>, Box<dyn Error>> {
             _id_zone: id_zone,
             _description: coffre.description.clone(),
             inventaire,
-            ouvert: ouvert,
-            visible : visible,
+            ouvert,
+            visible,
+            difficulte: coffre.difficulte,
+            outil: coffre.outil,
+            serrure: coffre.serrure,
+            cle: coffre.cle,
+            piege: coffre.piege,
         };
 
-        coffre_finales.entry(id_zone).or_insert(Vec::new()).push(c);
+        coffre_finales.entry(id_zone).or_default().push(c);
 
     }
 
@@ -206,10 +252,66 @@ pub fn charger_coffres() -> Result<HashMap<u8, Vec<Coffre>>, Box<dyn Error>> {
 }
 
 
+/// Écrit les zones et leurs coffres dans une paire de fichiers au format de
+/// `zone.json` et `coffre.json`, relisible par `charger_zones_depuis`.
+///
+/// Le contenu des coffres n'est pas écrit : il est tiré au chargement.
+///
+/// # Arguments
+///
+/// * `zones` - Les zones à écrire.
+/// * `chemin_zones` - Chemin du fichier des zones.
+/// * `chemin_coffres` - Chemin du fichier des coffres.
+///
+/// # Erreurs
+///
+/// Retourne une erreur si l'écriture échoue.
+pub fn ecrire_zones(zones: &[Zone], chemin_zones: &str, chemin_coffres: &str) -> Result<(), Box<dyn Error>> {
+    let mut zones_json = Vec::new();
+    let mut coffres_json = Vec::new();
+    for zone in zones {
+        zones_json.push(json!({
+            "type": "zone",
+            "nom": zone.nom,
+            "id": zone.id.to_string(),
+            "prix": zone.prix.to_string(),
+            "ouvert": zone.ouvert.to_string(),
+            "desc": zone.description,
+            "connection": zone.connection,
+            "objet_zone": { "taille": 255, "objets": [] },
+            "mob_present": zone.mob_present,
+        }));
+        for coffre in &zone.coffres {
+            let mut coffre_json = json!({
+                "type": "coffre",
+                "id": coffre._id.to_string(),
+                "id_zone": zone.id.to_string(),
+                "_ouvert": coffre.ouvert.to_string(),
+                "_visible": coffre.visible.to_string(),
+                "desc": coffre._description,
+                "_inventaire": [{ "_taille_texte": coffre.inventaire.taille.to_string(), "_objets": [] }],
+                "difficulte": coffre.difficulte,
+                "serrure": coffre.serrure,
+                "cle": coffre.cle,
+                "piege": coffre.piege,
+            });
+            if let Some(outil) = coffre.outil {
+                coffre_json["outil"] = json!(outil);
+            }
+            coffres_json.push(coffre_json);
+        }
+    }
+    fs::write(chemin_zones, serde_json::to_string_pretty(&zones_json)?)?;
+    fs::write(chemin_coffres, serde_json::to_string_pretty(&coffres_json)?)?;
+    Ok(())
+}
+
 /// Remplit aléatoirement les coffres avec des objets disponibles.
 ///
 /// Chaque coffre reçoit un nombre aléatoire d'objets (entre 1 et 5),
-/// avec au maximum 2 exemplaires de chaque objet.
+/// avec au maximum 2 exemplaires de chaque objet, dans la limite
+/// de sa capacité. Les objets de quête n'y sont jamais placés, et chaque
+/// arme ou équipement trouvé est un exemplaire tiré au hasard (`Instance::aleatoire`).
 ///
 /// # Arguments
 ///
@@ -217,7 +319,10 @@ pub fn charger_coffres() -> Result<HashMap<u8, Vec<Coffre>>, Box<dyn Error>> {
 pub fn remplir_coffres(coffres: &mut [Coffre]){
     let objets_disponibles = OBJETS_DISPONIBLES.read().unwrap();
     let mut rng = rand::rng();
-    let ids_objets: Vec<u8> = objets_disponibles.keys().cloned().collect();
+    let ids_objets: Vec<u8> = objets_disponibles.values()
+        .filter(|o| o.est_echangeable())
+        .map(|o| o.id)
+        .collect();
 
     for coffre in coffres.iter_mut() {
         let nb_objets = rng.random_range(1..=5);
@@ -228,14 +333,20 @@ pub fn remplir_coffres(coffres: &mut [Coffre]){
             if let Some(&objet_id) = ids_objets.choose(&mut rng) {
                 let compteur = tirages.entry(objet_id).or_insert(0);
                 if *compteur < 2 {
-                   *compteur += 1;
+                    let objet = &objets_disponibles[&objet_id];
+                    let exemplaire = ObjetInventaire {
+                        nombre: 1,
+                        objet_id,
+                        instance: objet.est_unique().then(|| Instance::aleatoire(objet, &mut rng)),
+                    };
+                    if coffre.inventaire.ajouter_exemplaire(exemplaire).is_err() {
+                        break;
+                    }
+                    *compteur += 1;
                     total_ajout += 1;
-                    coffre.inventaire.ajouter_objet(objet_id);
                 }
             }
         }
-
-        coffre.inventaire.taille = 5;
     }
 
 }
@@ -272,6 +383,29 @@ pub fn charger_objets() -> Result<(), Box<dyn Error>> {
                 vie: soin["vie"].as_str().unwrap().parse()?,
                 emplacement: soin["emplacement"].as_str().unwrap().parse()?,
             }
+        } else if let Some(cle) = objet_type_val.get("Cle") {
+            TypeObjet::Cle {
+                cible: cle.get("cible").and_then(|c| c.as_str()).map(str::parse).transpose()?,
+            }
+        } else if let Some(nourriture) = objet_type_val.get("Nourriture") {
+            TypeObjet::Nourriture {
+                vie: nourriture["vie"].as_str().unwrap().parse()?,
+            }
+        } else if objet_type_val.get("Quete").is_some() {
+            TypeObjet::Quete
+        } else if objet_type_val.get("Outil").is_some() {
+            TypeObjet::Outil
+        } else if objet_type_val.get("Materiau").is_some() {
+            TypeObjet::Materiau
+        } else if let Some(parchemin) = objet_type_val.get("Parchemin") {
+            TypeObjet::Parchemin {
+                experience: parchemin["experience"].as_str().unwrap().parse()?,
+            }
+        } else if let Some(projectile) = objet_type_val.get("Projectile") {
+            TypeObjet::Projectile {
+                degats: projectile["degats"].as_str().unwrap().parse()?,
+                proba_degats: projectile["proba_degats"].as_str().unwrap().parse()?,
+            }
         } else {
             return Err("Objet inconnu ou type manquant".into());
         };
@@ -279,4 +413,18 @@ pub fn charger_objets() -> Result<(), Box<dyn Error>> {
         ajouter_objet(id, nom, poids, prix, objet_type);
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Charge les quêtes depuis le fichier JSON et les ajoute à la collection globale.
+///
+/// # Erreurs
+///
+/// Retourne une erreur si le chargement ou la conversion échoue.
+pub fn charger_quetes() -> Result<(), Box<dyn Error>> {
+    let contenu = charger_json("src/json/quete.json")?;
+    let quetes: Vec<Quete> = serde_json::from_str(&contenu)?;
+    for quete in quetes {
+        ajouter_quete(quete);
+    }
+    Ok(())
+}
@@ -19,6 +19,12 @@ pub struct Objet {
 /// - `Arme` : Objet pouvant infliger des dégâts.
 /// - `Equipement` : Objet fournissant une protection et pouvant être équipé à un emplacement donné.
 /// - `Soin` : Objet permettant de restaurer de la vie, applicable à un emplacement.
+/// - `Cle` : Clé ouvrant des coffres ou un passage vers une zone.
+/// - `Nourriture` : Objet consommable restaurant la vie de tout le corps.
+/// - `Quete` : Objet de quête, qui ne peut être ni vendu ni jeté.
+/// - `Outil` : Objet utilisé automatiquement par certaines actions (crocheter, éclairer...).
+/// - `Parchemin` : Objet consommé à la lecture pour gagner de l'expérience.
+/// - `Projectile` : Objet lancé sur un adversaire au début d'un combat.
 #[derive(Debug, Clone)]
 pub enum TypeObjet {
     /// Arme avec dégâts, probabilité et fréquence des dégâts.
@@ -44,6 +50,35 @@ pub enum TypeObjet {
         /// Emplacement du corps auquel l'objet peut être appliqué.
         emplacement: Emplacement,
     },
+    /// Clé ouvrant les coffres dont la serrure lui correspond.
+    Cle {
+        /// Zone vers laquelle la clé ouvre un passage, s'il y en a une. Elle doit
+        /// correspondre aux exigences `Cle` de `zone.json` (voir `zone::verifier_cles`).
+        cible: Option<u8>,
+    },
+    /// Nourriture consommable.
+    Nourriture {
+        /// Points de vie restaurés sur chaque partie du corps non détruite.
+        vie: u32,
+    },
+    /// Objet de quête, qui ne peut être ni vendu ni jeté.
+    Quete,
+    /// Outil utilisé automatiquement par certaines actions.
+    Outil,
+    /// Parchemin consommé à la lecture.
+    Parchemin {
+        /// Expérience gagnée en lisant le parchemin.
+        experience: u32,
+    },
+    /// Matériau servant d'ingrédient aux recettes de fabrication.
+    Materiau,
+    /// Objet lancé sur un adversaire, consommé par le lancer.
+    Projectile {
+        /// Points de dégâts infligés.
+        degats: u32,
+        /// Probabilité de toucher la cible (entre 0.0 et 1.0).
+        proba_degats: f32,
+    },
 }
 
 /// Emplacements possibles où un équipement ou un soin peut être appliqué.
@@ -89,6 +124,10 @@ impl FromStr for Emplacement {
     }
 }
 
+/// Nombre maximal d'exemplaires par emplacement pour les objets empilables
+/// (soins, clés, objets inconnus du catalogue).
+pub const TAILLE_PILE_DEFAUT: u8 = 10;
+
 /// Collection globale et thread-safe des objets disponibles dans le jeu.
 ///
 /// Utilise un verrou en lecture/écriture pour la synchronisation.
@@ -103,7 +142,7 @@ pub static OBJETS_DISPONIBLES: Lazy<RwLock<HashMap<u8, Objet>>> = Lazy::new(|| R
 /// * `nom` - Nom de l'objet.
 /// * `poids` - Poids de l'objet.
 /// * `prix` - Prix de l'objet.
-/// * `objet_type` - Type de l'objet (arme, équipement, soin).
+/// * `objet_type` - Type de l'objet (arme, équipement, soin, clé...).
 pub fn ajouter_objet(id: u8, nom: String, poids: u32, prix: u32, objet_type: TypeObjet) {
     let mut objets = OBJETS_DISPONIBLES.write().unwrap();
     objets.insert(id, Objet { id, nom, poids, prix, objet_type });
@@ -137,6 +176,34 @@ impl fmt::Display for Objet {
                 writeln!(f, "  - Restauration de vie : {}", vie)?;
                 writeln!(f, "  - Emplacement         : {:?}", emplacement)?;
             }
+            TypeObjet::Cle { cible } => {
+                writeln!(f, "- Type : Clé")?;
+                if let Some(zone_id) = cible {
+                    writeln!(f, "  - Ouvre le passage vers la zone {}", zone_id)?;
+                }
+            }
+            TypeObjet::Nourriture { vie } => {
+                writeln!(f, "- Type : Nourriture")?;
+                writeln!(f, "  - Restauration de vie : {}", vie)?;
+            }
+            TypeObjet::Quete => {
+                writeln!(f, "- Type : Objet de quête (ni vendable, ni jetable)")?;
+            }
+            TypeObjet::Outil => {
+                writeln!(f, "- Type : Outil")?;
+            }
+            TypeObjet::Parchemin { experience } => {
+                writeln!(f, "- Type : Parchemin")?;
+                writeln!(f, "  - Expérience : {}", experience)?;
+            }
+            TypeObjet::Materiau => {
+                writeln!(f, "- Type : Matériau de fabrication")?;
+            }
+            TypeObjet::Projectile { degats, proba_degats } => {
+                writeln!(f, "- Type : Projectile")?;
+                writeln!(f, "  - Dégâts      : {}", degats)?;
+                writeln!(f, "  - Probabilité : {:.2}", proba_degats)?;
+            }
         }
         Ok(())
     }
@@ -165,6 +232,31 @@ impl Objet {
         matches!(self.objet_type, TypeObjet::Soin { .. })
     }
 
+    /// Vérifie si l'objet peut être vendu ou jeté : les objets de quête ne le peuvent pas.
+    pub fn est_echangeable(&self) -> bool {
+        !matches!(self.objet_type, TypeObjet::Quete)
+    }
+
+    /// Vérifie si l'objet est unique : chaque exemplaire d'une arme ou d'un
+    /// équipement portable a ses propres propriétés (`Instance`) et occupe
+    /// son propre emplacement.
+    pub fn est_unique(&self) -> bool {
+        self.est_arme() || self.est_equipement()
+    }
+
+    /// Renvoie le nombre maximal d'exemplaires de cet objet pouvant être
+    /// empilés dans un même emplacement d'inventaire.
+    ///
+    /// Les objets uniques ne s'empilent pas, les autres objets
+    /// utilisent `TAILLE_PILE_DEFAUT`.
+    pub fn taille_pile(&self) -> u8 {
+        if self.est_unique() {
+            1
+        } else {
+            TAILLE_PILE_DEFAUT
+        }
+    }
+
     /// Récupère l'emplacement associé à l'objet s'il existe.
     ///
     /// Retourne `Some(Emplacement)` pour les équipements et soins, sinon `None`.
@@ -231,4 +323,30 @@ mod tests {
         assert!(!soin.est_arme());
         assert!(!soin.est_equipement());
     }
+
+    #[test]
+    fn test_nouvelles_categories() {
+        let quete = Objet {
+            id: 4,
+            nom: "Lettre".to_string(),
+            poids: 1,
+            prix: 0,
+            objet_type: TypeObjet::Quete,
+        };
+        assert!(!quete.est_echangeable());
+        assert_eq!(quete.emplacement(), None);
+        assert!(format!("{}", quete).contains("Objet de quête"));
+
+        let cle = Objet {
+            id: 5,
+            nom: "Clé".to_string(),
+            poids: 1,
+            prix: 10,
+            objet_type: TypeObjet::Cle { cible: Some(9) },
+        };
+        assert!(cle.est_echangeable());
+        assert!(!cle.est_equipement());
+        assert_eq!(cle.taille_pile(), TAILLE_PILE_DEFAUT);
+        assert!(format!("{}", cle).contains("zone 9"));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,92 @@
+use std::sync::Once;
+use crate::inventaire::{Inventaire, ObjetInventaire};
+use crate::objet::{ajouter_objet, Emplacement, TypeObjet};
+use crate::personnage::Personnage;
+use crate::zone::Zone;
+
+/// Objet sans effet de 1,5 kg.
+pub const ENCLUME: u8 = 201;
+/// Objet sans effet de 4 kg.
+pub const ROCHER: u8 = 202;
+/// Arme de 1 kg infligeant 5 dégâts.
+pub const HACHE: u8 = 203;
+/// Arme légère (100 g) infligeant 5 dégâts.
+pub const LANCE: u8 = 208;
+/// Objet sans effet et sans poids.
+pub const PIERRE: u8 = 209;
+/// Ingrédient de fabrication.
+pub const FIL: u8 = 210;
+/// Produit de fabrication.
+pub const CORDE: u8 = 211;
+/// Objet de quête.
+pub const RELIQUE: u8 = 212;
+/// Projectile infligeant 5 dégâts.
+pub const CAILLOU: u8 = 213;
+/// Arme de 1 kg infligeant 10 dégâts.
+pub const SABRE: u8 = 214;
+/// Identifiant absent de tout catalogue.
+pub const INCONNU: u8 = 250;
+
+static CATALOGUE: Once = Once::new();
+
+/// Ajoute au catalogue `OBJETS_DISPONIBLES` les objets fictifs des tests.
+///
+/// Leurs identifiants (201 et plus) ne recouvrent pas ceux de `objet.json` ; le
+/// catalogue n'est rempli qu'une fois, quel que soit le nombre de tests qui l'appellent.
+pub fn catalogue_test() {
+    CATALOGUE.call_once(|| {
+        let aucun = || TypeObjet::Soin { vie: 0, emplacement: Emplacement::Aucun };
+        ajouter_objet(ENCLUME, "Enclume".to_string(), 1500, 10, aucun());
+        ajouter_objet(ROCHER, "Rocher".to_string(), 4000, 1, aucun());
+        ajouter_objet(HACHE, "Hache".to_string(), 1000, 10, TypeObjet::Arme { degats: 5, proba_degats: 1.0, frequence_degats: 1 });
+        ajouter_objet(LANCE, "Lance".to_string(), 100, 1, TypeObjet::Arme { degats: 5, proba_degats: 1.0, frequence_degats: 1 });
+        ajouter_objet(PIERRE, "Pierre".to_string(), 0, 1, aucun());
+        ajouter_objet(FIL, "Fil".to_string(), 10, 1, aucun());
+        ajouter_objet(CORDE, "Corde".to_string(), 10, 1, aucun());
+        ajouter_objet(RELIQUE, "Relique".to_string(), 10, 100, TypeObjet::Quete);
+        ajouter_objet(CAILLOU, "Caillou".to_string(), 100, 1, TypeObjet::Projectile { degats: 5, proba_degats: 1.0 });
+        ajouter_objet(SABRE, "Sabre".to_string(), 1000, 10, TypeObjet::Arme { degats: 10, proba_degats: 1.0, frequence_degats: 1 });
+    });
+}
+
+/// Crée un joueur de test sans argent ni parties du corps, aux attributs par défaut.
+///
+/// # Arguments
+/// * `objets` - Contenu de l'inventaire.
+/// * `taille` - Nombre d'emplacements de l'inventaire.
+pub fn joueur_test(objets: Vec<ObjetInventaire>, taille: u8) -> Personnage {
+    Personnage {
+        id: 1,
+        nom: "Test".to_string(),
+        description: "desc".to_string(),
+        force: 10,
+        inventaire: Inventaire { taille, objets },
+        parties_du_corps: vec![],
+        argent: 0,
+        est_vivant: true,
+        progression: Default::default(),
+        attributs: Default::default(),
+    }
+}
+
+/// Crée une zone de test ouverte, vide et sans connexion.
+///
+/// # Arguments
+/// * `id` - Identifiant de la zone.
+pub fn zone_test(id: u8) -> Zone {
+    Zone {
+        id,
+        nom: "TestZone".to_string(),
+        ouvert: true,
+        description: "desc".to_string(),
+        connection: vec![],
+        coffres: vec![],
+        objet_zone: Inventaire { taille: 1, objets: vec![] },
+        mob_present: false,
+        mob: None,
+        reapparition: None,
+        prix: 0,
+        tentatives_fouille: 0,
+        meteo: None,
+    }
+}
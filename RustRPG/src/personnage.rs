@@ -1,3 +1,4 @@
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
 use std::fs::{File};
 use std::io::{self, Read, Write};
@@ -5,9 +6,51 @@ use serde::{Serialize, Deserialize};
 use chrono::{Utc, DateTime};
 use rand::Rng;
 use rand::rngs::ThreadRng;
-use crate::inventaire::{Inventaire, ObjetInventaire};
+use rand::prelude::IndexedRandom;
+use crate::inventaire::{ErreurInventaire, Inventaire, ObjetInventaire};
 use crate::objet::OBJETS_DISPONIBLES;
 use crate::Zone;
+use crate::progression::{CourbeProgression, EtatPnj, Progression, COURBE_PROGRESSION};
+use crate::dialogue::{ArbreDialogue, ChoixDialogue, Effet};
+use crate::classe::Classe;
+
+/// Charge (en grammes) que peut porter un personnage par point de force.
+const POIDS_PAR_FORCE: u32 = 100;
+/// Multiplicateur de la charge maximale au-delà duquel plus rien ne peut être ramassé.
+const LIMITE_SURCHARGE: f32 = 1.5;
+/// Modificateur appliqué à la force effective d'un personnage surchargé.
+const MALUS_SURCHARGE: f32 = 0.75;
+/// Part du prix de base d'un objet qu'un marchand accepte de payer pour le racheter.
+const RATIO_RACHAT: f32 = 0.5;
+/// Délai (en minutes) entre deux réapprovisionnements du stock d'un marchand.
+const INTERVALLE_REAPPROVISIONNEMENT: i64 = 10;
+/// Bornes de la réputation du joueur auprès d'un PNJ.
+const REPUTATION_MIN: i32 = -100;
+const REPUTATION_MAX: i32 = 100;
+/// Réputation en dessous de laquelle un PNJ refuse de commercer.
+const SEUIL_REFUS_COMMERCE: i32 = -50;
+/// Amende demandée par un garde pour chaque crime commis.
+const AMENDE_PAR_CRIME: u32 = 50;
+/// Variation maximale des prix due à la réputation (20 % à ±100).
+const INFLUENCE_REPUTATION: f32 = 0.2;
+/// Remise obtenue par un marchandage réussi.
+const REMISE_MARCHANDAGE: f32 = 0.1;
+/// Remise accordée par les marchands d'une zone que le joueur possède.
+const REMISE_PROPRIETAIRE: f32 = 0.15;
+/// Valeur moyenne des attributs, utilisée par défaut pour les anciens personnages.
+pub const ATTRIBUT_DEFAUT: u8 = 50;
+/// Variation maximale des prix due au charisme (10 % à 0 ou 100).
+const INFLUENCE_CHARISME: f32 = 0.1;
+/// Durée réelle (en secondes) d'une heure de jeu : une journée dure 24 minutes.
+const SECONDES_PAR_HEURE_JEU: i64 = 60;
+
+/// Renvoie l'heure de jeu (0 à 23) correspondant à une date réelle.
+///
+/// # Arguments
+/// * `maintenant` - Date courante.
+pub fn heure_jeu(maintenant: DateTime<Utc>) -> u8 {
+    (maintenant.timestamp().div_euclid(SECONDES_PAR_HEURE_JEU) % 24) as u8
+}
 
 /// Représente l’état d’une partie du corps.
 ///
@@ -100,9 +143,13 @@ impl PartieDuCorps {
     /// Met à jour la vie actuelle, l’état (`Blessee` ou `Morte`),
     /// et calcule un délai de guérison.
     ///
+    /// # Arguments
+    /// * `degats` - Les dégâts subis.
+    /// * `facteur_guerison` - Multiplicateur du délai de guérison (endurance du personnage).
+    ///
     /// # Retour
     /// `true` si la partie est détruite, `false` sinon.
-    pub fn subir_degats(&mut self, degats: u32) -> bool {
+    pub fn subir_degats(&mut self, degats: u32, facteur_guerison: f32) -> bool {
         if self.est_morte() {
             return false;
         }
@@ -121,7 +168,7 @@ impl PartieDuCorps {
         
         if pourcentage_blessure > 0 {
             self.etat = EtatPartie::Blessee(pourcentage_blessure);
-            let temps_guerison = chrono::Duration::minutes(pourcentage_blessure as i64);
+            let temps_guerison = chrono::Duration::seconds((60.0 * pourcentage_blessure as f32 * facteur_guerison) as i64);
             self.guerison = Utc::now() + temps_guerison;
             
             println!("{} est blessée à {}% (vie: {}/{})", 
@@ -141,9 +188,24 @@ impl PartieDuCorps {
         &self.equipement
     }
 
-    /// Ajoute un objet à l’équipement de cette partie.
-    pub fn ajouter_equipement(&mut self, objet : u8){
-        let _ = &self.equipement.ajouter_objet(objet);
+    /// Ajoute un exemplaire d'objet à l’équipement de cette partie, avec ses propriétés.
+    ///
+    /// Une partie du corps ne porte qu'un seul objet à la fois : retourne
+    /// `Err(ErreurInventaire::Plein)` si elle est déjà équipée.
+    pub fn ajouter_equipement(&mut self, objet : ObjetInventaire) -> Result<(), ErreurInventaire> {
+        if !self.equipement.objets.is_empty() {
+            return Err(ErreurInventaire::Plein);
+        }
+        self.equipement.ajouter_exemplaire(ObjetInventaire { nombre: 1, ..objet })
+    }
+
+    /// Fait perdre un point de durabilité à l'objet porté par cette partie.
+    ///
+    /// # Retour
+    /// L'identifiant de l'objet s'il vient de se casser.
+    pub fn abimer_equipement(&mut self) -> Option<u8> {
+        let objet = self.equipement.objets.first_mut()?;
+        objet.abimer().then_some(objet.objet_id)
     }
 
     /// Récupère un objet de l’inventaire à un index donné.
@@ -160,6 +222,15 @@ impl PartieDuCorps {
         objet
     }
 
+    /// Augmente la vie maximale de la partie ; la vie actuelle augmente d'autant
+    /// si la partie n'est pas détruite.
+    pub fn augmenter_vie_max(&mut self, gain: u32) {
+        self.vie_max += gain;
+        if !self.est_morte() {
+            self.vie_actuelle += gain;
+        }
+    }
+
     /// Renvoie la vie actuelle.
     pub fn vie_actuelle(&self) -> u32 {
         self.vie_actuelle
@@ -181,6 +252,25 @@ impl PartieDuCorps {
     }
 }
 
+/// Caractéristique améliorable avec les points gagnés en montant de niveau.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Caracteristique {
+    /// Augmente la force (dégâts et charge maximale).
+    Force,
+    /// Augmente la vie maximale de toutes les parties du corps.
+    Vie,
+    /// Augmente la charge maximale.
+    Charge,
+    /// Augmente l'agilité.
+    Agilite,
+    /// Augmente l'endurance.
+    Endurance,
+    /// Augmente la perception.
+    Perception,
+    /// Augmente le charisme.
+    Charisme,
+}
+
 /// Représente le résultat d'une blessure infligée à un personnage.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ResultatBlessure {
@@ -213,6 +303,63 @@ pub struct Personnage {
     pub argent: u32,
     /// Indique si le personnage est vivant.
     pub est_vivant: bool,
+    /// Avancement du joueur (drapeaux d'histoire), vide pour les PNJs et les mobs.
+    #[serde(default)]
+    pub progression: Progression,
+    /// Attributs secondaires (agilité, endurance, perception, charisme).
+    #[serde(default)]
+    pub attributs: Attributs,
+}
+
+/// Attributs d'un personnage en plus de la force, de 0 à 100.
+///
+/// Les personnages sauvegardés avant leur ajout reçoivent `ATTRIBUT_DEFAUT` partout.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Attributs {
+    /// Initiative en combat, esquive et fuite.
+    #[serde(default = "attribut_defaut")]
+    pub agilite: u8,
+    /// Vie des parties du corps à la création et vitesse de guérison.
+    #[serde(default = "attribut_defaut")]
+    pub endurance: u8,
+    /// Chance de trouver les coffres cachés en fouillant une zone.
+    #[serde(default = "attribut_defaut")]
+    pub perception: u8,
+    /// Prix des marchands et chance de marchander.
+    #[serde(default = "attribut_defaut")]
+    pub charisme: u8,
+}
+
+fn attribut_defaut() -> u8 {
+    ATTRIBUT_DEFAUT
+}
+
+/// Augmente un attribut sans dépasser 100.
+fn augmenter_attribut(valeur: u8, gain: u8) -> u8 {
+    valeur.saturating_add(gain).min(100)
+}
+
+impl Default for Attributs {
+    fn default() -> Self {
+        Attributs {
+            agilite: ATTRIBUT_DEFAUT,
+            endurance: ATTRIBUT_DEFAUT,
+            perception: ATTRIBUT_DEFAUT,
+            charisme: ATTRIBUT_DEFAUT,
+        }
+    }
+}
+
+impl Attributs {
+    /// Tire des attributs aléatoires autour de `ATTRIBUT_DEFAUT` (30 à 70).
+    pub fn aleatoires(rng: &mut ThreadRng) -> Self {
+        Attributs {
+            agilite: rng.random_range(30..=70),
+            endurance: rng.random_range(30..=70),
+            perception: rng.random_range(30..=70),
+            charisme: rng.random_range(30..=70),
+        }
+    }
 }
 
 impl fmt::Display for Personnage {
@@ -238,10 +385,12 @@ impl Personnage {
     /// * `nom_partie` - Le nom de la partie ciblée.
     /// * `degats` - Le montant de dégâts infligés.
     pub fn gerer_blessure(&mut self, nom_partie: &str, degats: u32) -> ResultatBlessure {
+        let facteur_guerison = self.facteur_guerison();
         if let Some(partie) = self.parties_du_corps.iter_mut()
             .find(|p| p.nom.to_lowercase() == nom_partie.to_lowercase()) {
             
-            let partie_detruite = partie.subir_degats(degats);
+            // L'endurance accélère ou ralentit la guérison
+            let partie_detruite = partie.subir_degats(degats, facteur_guerison);
             
             if partie_detruite && (partie.nom.to_lowercase().contains("tête") || partie.nom.to_lowercase().contains("torse")) {
                 self.est_vivant = false;
@@ -303,9 +452,129 @@ impl Personnage {
             }
         }
 
+        if self.est_surcharge() {
+            modificateur *= MALUS_SURCHARGE;
+        }
+
         ((self.force as f32) * modificateur) as u8
     }
 
+    /// Calcule le poids total porté (en grammes) : inventaire et équipement
+    /// de toutes les parties du corps.
+    pub fn poids_porte(&self) -> u32 {
+        let poids_equipement: u32 = self.parties_du_corps.iter()
+            .map(|p| p.equipement.poids_total())
+            .sum();
+        self.inventaire.poids_total() + poids_equipement
+    }
+
+    /// Indique si le personnage a l'objet sur lui, dans son inventaire ou équipé
+    /// sur une partie du corps.
+    ///
+    /// # Arguments
+    /// * `objet_id` - Identifiant de l'objet recherché.
+    pub fn a_sur_lui(&self, objet_id: u8) -> bool {
+        self.inventaire.objets.iter()
+            .chain(self.parties_du_corps.iter().flat_map(|p| p.equipement.objets.iter()))
+            .any(|o| o.objet_id == objet_id)
+    }
+
+    /// Renvoie la charge maximale (en grammes) que le personnage peut porter
+    /// sans être ralenti, dérivée de sa force et des points répartis en charge.
+    pub fn poids_max(&self) -> u32 {
+        self.force as u32 * POIDS_PAR_FORCE + self.progression.bonus_charge
+    }
+
+    /// Renvoie le multiplicateur des délais de guérison : 1 à `ATTRIBUT_DEFAUT`
+    /// d'endurance, plus court au-dessus, plus long en dessous.
+    pub fn facteur_guerison(&self) -> f32 {
+        ATTRIBUT_DEFAUT as f32 / self.attributs.endurance.max(1) as f32
+    }
+
+    /// Renvoie la probabilité d'esquiver une attaque, dérivée de l'agilité (25 % au maximum).
+    pub fn chance_esquive(&self) -> f64 {
+        self.attributs.agilite as f64 / 400.0
+    }
+
+    /// Renvoie la probabilité de fuir face à un adversaire, selon la différence d'agilité.
+    ///
+    /// # Arguments
+    /// * `adversaire` - Le personnage que l'on tente de fuir.
+    pub fn chance_fuite(&self, adversaire: &Personnage) -> f64 {
+        let ecart = self.attributs.agilite as f64 - adversaire.attributs.agilite as f64;
+        (0.5 + ecart / 200.0).clamp(0.1, 0.9)
+    }
+
+    /// Ajoute de l'expérience au personnage et annonce les niveaux gagnés.
+    ///
+    /// # Arguments
+    /// * `experience` - Expérience gagnée.
+    pub fn gagner_experience(&mut self, experience: u32) {
+        println!("✨ Vous gagnez {} points d'expérience.", experience);
+        let niveaux = self.progression.ajouter_experience(experience, &COURBE_PROGRESSION);
+        if niveaux > 0 {
+            println!("⬆️ Niveau supérieur ! Vous êtes maintenant niveau {} ({} point(s) à répartir, commande n).",
+                self.progression.niveau, self.progression.points_a_repartir);
+        }
+    }
+
+    /// Dépense un point de niveau pour améliorer une caractéristique.
+    ///
+    /// # Arguments
+    /// * `caracteristique` - La caractéristique à améliorer.
+    /// * `courbe` - La courbe de progression donnant les gains par point.
+    ///
+    /// # Retour
+    /// `Err` si le personnage n'a plus de point à répartir.
+    pub fn repartir_point(&mut self, caracteristique: Caracteristique, courbe: &CourbeProgression) -> Result<(), String> {
+        if self.progression.points_a_repartir == 0 {
+            return Err("Vous n'avez aucun point à répartir".to_string());
+        }
+        match caracteristique {
+            Caracteristique::Force => self.force = self.force.saturating_add(courbe.gain_force),
+            Caracteristique::Vie => {
+                for partie in self.parties_du_corps.iter_mut() {
+                    partie.augmenter_vie_max(courbe.gain_vie);
+                }
+            }
+            Caracteristique::Charge => self.progression.bonus_charge += courbe.gain_charge,
+            Caracteristique::Agilite => self.attributs.agilite = augmenter_attribut(self.attributs.agilite, courbe.gain_attribut),
+            Caracteristique::Endurance => self.attributs.endurance = augmenter_attribut(self.attributs.endurance, courbe.gain_attribut),
+            Caracteristique::Perception => self.attributs.perception = augmenter_attribut(self.attributs.perception, courbe.gain_attribut),
+            Caracteristique::Charisme => self.attributs.charisme = augmenter_attribut(self.attributs.charisme, courbe.gain_attribut),
+        }
+        self.progression.points_a_repartir -= 1;
+        Ok(())
+    }
+
+    /// Vérifie si le personnage porte plus que sa charge maximale.
+    pub fn est_surcharge(&self) -> bool {
+        self.poids_porte() > self.poids_max()
+    }
+
+    /// Vérifie si le personnage peut ramasser `poids_supplementaire` grammes
+    /// sans dépasser la limite absolue (`LIMITE_SURCHARGE` fois la charge maximale).
+    pub fn peut_porter(&self, poids_supplementaire: u32) -> bool {
+        let limite = (self.poids_max() as f32 * LIMITE_SURCHARGE) as u32;
+        self.poids_porte() + poids_supplementaire <= limite
+    }
+
+    /// Renvoie la durée d'un déplacement entre deux zones, allongée en cas de surcharge.
+    pub fn duree_deplacement(&self) -> std::time::Duration {
+        if self.est_surcharge() {
+            std::time::Duration::from_secs(8)
+        } else {
+            std::time::Duration::from_secs(5)
+        }
+    }
+
+    /// Renvoie la durée d'une fouille de zone : 6 secondes sans perception,
+    /// 2 secondes à perception maximale.
+    pub fn duree_fouille(&self) -> std::time::Duration {
+        let perception = self.attributs.perception.min(100) as u64;
+        std::time::Duration::from_millis(6000 - perception * 40)
+    }
+
     /// Lit un fichier JSON contenant une liste de personnages.
     ///
     /// Retourne `Ok(Some(vec))` si des personnages sont trouvés,
@@ -335,10 +604,7 @@ impl Personnage {
     ///
     /// * `fichier` - Le chemin du fichier dans lequel sauvegarder.
     pub fn sauvegarder_json(&self, fichier: &str) -> io::Result<()> {
-        let mut personnages = match Self::lire_fichier_json(fichier)? {
-            Some(personnages) => personnages,
-            None => vec![],
-        };
+        let mut personnages = Self::lire_fichier_json(fichier)?.unwrap_or_default();
         
         personnages.push(self.clone());
         let json = serde_json::to_string_pretty(&personnages)?;
@@ -346,6 +612,24 @@ impl Personnage {
         file.write_all(json.as_bytes())
     }
 
+    /// Met à jour ce personnage dans un fichier JSON : l'entrée ayant le même
+    /// identifiant est remplacée, ou le personnage est ajouté s'il n'y figure pas.
+    ///
+    /// # Arguments
+    ///
+    /// * `fichier` - Le chemin du fichier dans lequel sauvegarder.
+    pub fn mettre_a_jour_json(&self, fichier: &str) -> io::Result<()> {
+        let mut personnages = Self::lire_fichier_json(fichier)?.unwrap_or_default();
+
+        match personnages.iter_mut().find(|p| p.id == self.id) {
+            Some(personnage) => *personnage = self.clone(),
+            None => personnages.push(self.clone()),
+        }
+        let json = serde_json::to_string_pretty(&personnages)?;
+        let mut file = File::create(fichier)?;
+        file.write_all(json.as_bytes())
+    }
+
     /// Charge une liste de personnages à partir d’un fichier JSON.
     /// Crée des personnages de test si le fichier est vide ou manquant.
     pub fn charger_depuis_json(fichier: &str) -> io::Result<Vec<Personnage>> {
@@ -370,10 +654,7 @@ impl Personnage {
 
     /// Calcule le prochain identifiant disponible en fonction des personnages existants.
     pub fn prochain_id(fichier: &str) -> io::Result<u32> {
-        let personnages = match Self::lire_fichier_json(fichier)? {
-            Some(personnages) => personnages,
-            None => vec![],
-        };
+        let personnages = Self::lire_fichier_json(fichier)?.unwrap_or_default();
         let max_id = personnages.iter().map(|p| p.id).max().unwrap_or(0);
         Ok(max_id + 1)
     }
@@ -392,17 +673,93 @@ impl Personnage {
         }
     }
 
+    /// Repose le personnage : ses parties blessées guérissent complètement,
+    /// mais une partie détruite ne repousse pas.
+    ///
+    /// # Retour
+    /// Le nombre de parties soignées.
+    pub fn se_reposer(&mut self) -> usize {
+        let maintenant = chrono::Utc::now();
+        let mut soignees = 0;
+        for partie in &mut self.parties_du_corps {
+            if !partie.est_morte() && partie.vie_actuelle < partie.vie_max {
+                partie.vie_actuelle = partie.vie_max;
+                partie.etat = EtatPartie::Saine;
+                partie.guerison = maintenant;
+                soignees += 1;
+            }
+        }
+        soignees
+    }
+
+    /// Restaure jusqu'à `vie` points sur chaque partie du corps blessée
+    /// (nourriture). Une partie détruite ne se régénère pas.
+    ///
+    /// # Retour
+    /// Le total des points de vie restaurés.
+    pub fn restaurer_vie(&mut self, vie: u32) -> u32 {
+        let mut total = 0;
+        for partie in &mut self.parties_du_corps {
+            if partie.est_morte() || partie.vie_actuelle >= partie.vie_max {
+                continue;
+            }
+            let gain = vie.min(partie.vie_max - partie.vie_actuelle);
+            partie.vie_actuelle += gain;
+            total += gain;
+            if partie.vie_actuelle == partie.vie_max {
+                partie.etat = EtatPartie::Saine;
+                partie.guerison = chrono::Utc::now();
+            } else {
+                partie.etat = EtatPartie::Blessee(100 - partie.pourcentage_vie() as u8);
+            }
+        }
+        total
+    }
+
+    /// Équipe l'objet d'index `index_objet` de l'inventaire sur la partie du corps
+    /// `index_partie`, à la place de l'objet qu'elle porte éventuellement.
+    ///
+    /// L'ancien objet retourne dans l'inventaire, ou est posé au sol si l'inventaire
+    /// est plein. Rien n'est modifié si l'une de ces étapes échoue.
+    ///
+    /// # Arguments
+    /// * `index_objet` - Index de l'objet dans l'inventaire.
+    /// * `index_partie` - Index de la partie du corps à équiper.
+    /// * `sol` - Les objets au sol de la zone, qui reçoivent l'ancien objet si besoin.
+    ///
+    /// # Retour
+    /// `Ok(true)` si l'ancien objet a été posé au sol, `Err` avec l'explication à afficher sinon.
+    pub fn equiper(&mut self, index_objet: usize, index_partie: usize, sol: &mut Inventaire) -> Result<bool, String> {
+        let mut inventaire = self.inventaire.clone();
+        let mut partie = self.parties_du_corps[index_partie].clone();
+        let ancien = (!partie.equipement.objets.is_empty()).then(|| partie.récupérer_objet(0));
+        let objet = inventaire.retirer_exemplaires(index_objet, 1);
+        partie.ajouter_equipement(objet).map_err(|e| format!("Impossible d'équiper l'objet : {}", e))?;
+        let mut au_sol = false;
+        if let Some(ancien) = ancien {
+            if inventaire.ajouter_exemplaire(ancien.clone()).is_err() {
+                sol.ajouter_exemplaire(ancien)
+                    .map_err(|_| "Inventaire plein et plus de place au sol pour l'ancien équipement.".to_string())?;
+                au_sol = true;
+            }
+        }
+        self.inventaire = inventaire;
+        self.parties_du_corps[index_partie] = partie;
+        Ok(au_sol)
+    }
+
     /// Soigne toutes les parties du corps après un combat.
     pub fn soigner_apres_combat(&mut self) {
         if !self.est_vivant {
             return;
         }
         let maintenant = chrono::Utc::now();
+        let facteur_guerison = self.facteur_guerison();
         for partie in &mut self.parties_du_corps {
             if partie.est_morte() {
                 partie.vie_actuelle = partie.vie_max;
                 partie.etat = EtatPartie::Saine;
-                partie.guerison = maintenant + chrono::Duration::hours(1);
+                partie.guerison = maintenant + chrono::Duration::seconds((3600.0 * facteur_guerison) as i64);
             } else if partie.vie_actuelle < partie.vie_max {
                 partie.vie_actuelle = partie.vie_max;
                 partie.etat = EtatPartie::Saine;
@@ -413,14 +770,18 @@ impl Personnage {
 }
 
 /// Crée et retourne les parties du corps standards d’un personnage.
-fn creer_parties_du_corps() -> Vec<PartieDuCorps> {
+///
+/// La vie de chaque partie est ajustée selon l'endurance : inchangée à
+/// `ATTRIBUT_DEFAUT`, +50 % à 100.
+fn creer_parties_du_corps(endurance: u8) -> Vec<PartieDuCorps> {
+    let vie = |base: u32| base * (ATTRIBUT_DEFAUT as u32 + endurance as u32) / (2 * ATTRIBUT_DEFAUT as u32);
     vec![
-        PartieDuCorps::new("Tête".to_string(), 50),
-        PartieDuCorps::new("Torse".to_string(), 100),
-        PartieDuCorps::new("Bras droit".to_string(), 75),
-        PartieDuCorps::new("Bras gauche".to_string(), 75),
-        PartieDuCorps::new("Jambe droite".to_string(), 80),
-        PartieDuCorps::new("Jambe gauche".to_string(), 80),
+        PartieDuCorps::new("Tête".to_string(), vie(50)),
+        PartieDuCorps::new("Torse".to_string(), vie(100)),
+        PartieDuCorps::new("Bras droit".to_string(), vie(75)),
+        PartieDuCorps::new("Bras gauche".to_string(), vie(75)),
+        PartieDuCorps::new("Jambe droite".to_string(), vie(80)),
+        PartieDuCorps::new("Jambe gauche".to_string(), vie(80)),
     ]
 }
 
@@ -433,15 +794,143 @@ pub struct Joueur {
 
 /// Représente un personnage non-joueur (PNJ), comme un marchand ou un habitant.
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[allow(clippy::upper_case_acronyms)]
 pub struct PNJ {
     /// Le personnage associé au PNJ.
     pub personnage: Personnage,
-    /// Liste des dialogues que le PNJ peut prononcer.
+    /// Liste des répliques simples que le PNJ peut prononcer.
     pub dialogues: Vec<String>,
     /// Identifiant de la zone où se trouve ce PNJ.
     pub zone_id: u32,
     /// Multiplicateur appliqué aux prix (ex. : pour vendre plus cher).
     pub multiplicateur_prix: f32,
+    /// Stock de référence du marchand, vers lequel son inventaire se réapprovisionne.
+    #[serde(default)]
+    pub stock: Vec<ObjetInventaire>,
+    /// Date du dernier réapprovisionnement du stock.
+    #[serde(default = "Utc::now")]
+    pub dernier_reapprovisionnement: DateTime<Utc>,
+    /// Réputation du joueur auprès de ce PNJ, entre `REPUTATION_MIN` et `REPUTATION_MAX`,
+    /// sauvegardée dans la progression du joueur.
+    #[serde(default)]
+    pub reputation: i32,
+    /// Remise obtenue en marchandant, valable jusqu'à la fin de la visite en boutique.
+    #[serde(skip)]
+    pub remise: f32,
+    /// Indique si le joueur a échappé à ce PNJ, qui ne l'attaque plus tant qu'ils
+    /// se trouvent dans la même zone.
+    #[serde(skip)]
+    pub joueur_enfui: bool,
+    /// Arbre de dialogue du PNJ ; s'il est absent, un arbre par défaut est construit
+    /// à partir de `dialogues`.
+    #[serde(default)]
+    pub arbre_dialogue: Option<ArbreDialogue>,
+    /// Façon dont le PNJ se déplace entre les zones.
+    #[serde(default)]
+    pub deplacement: Deplacement,
+    /// Tendance du PNJ à attaquer le joueur de lui-même.
+    #[serde(default)]
+    pub agressivite: Agressivite,
+    /// Indique si le PNJ est un garde, qui attaque le joueur ayant commis des crimes.
+    #[serde(default)]
+    pub garde: bool,
+}
+
+/// Tendance d'un mob ou d'un PNJ à attaquer le joueur de lui-même.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum Agressivite {
+    /// N'attaque jamais le premier.
+    #[default]
+    Pacifique,
+    /// Attaque parfois l'intrus ; un PNJ territorial attaque le joueur qu'il déteste.
+    Territorial,
+    /// Attaque presque toujours et poursuit le joueur qui s'enfuit.
+    Agressif,
+}
+
+impl Agressivite {
+    /// Probabilité qu'un mob attaque le joueur qui entre dans sa zone.
+    pub fn chance_attaque(&self) -> f64 {
+        match self {
+            Agressivite::Pacifique => 0.0,
+            Agressivite::Territorial => 0.5,
+            Agressivite::Agressif => 0.9,
+        }
+    }
+
+    /// Indique si le mob suit le joueur dans la zone voisine.
+    pub fn poursuit(&self) -> bool {
+        *self == Agressivite::Agressif
+    }
+}
+
+/// Façon dont un PNJ se déplace entre les zones.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub enum Deplacement {
+    /// Le PNJ reste dans sa zone.
+    #[default]
+    Fixe,
+    /// Le PNJ suit un emploi du temps : il se rend dans la zone de la dernière
+    /// étape commencée à l'heure de jeu actuelle.
+    Horaire { etapes: Vec<EtapeHoraire> },
+    /// Le PNJ part vers une zone voisine au hasard avec la probabilité `chance`
+    /// à chaque action du joueur.
+    Errant { chance: f64 },
+}
+
+/// Étape de l'emploi du temps d'un PNJ.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EtapeHoraire {
+    /// Heure de jeu (0 à 23) à laquelle le PNJ se rend dans la zone.
+    pub heure: u8,
+    /// Identifiant de la zone de destination.
+    pub zone_id: u32,
+}
+
+/// Renvoie la première zone du plus court chemin d'une zone à une autre, en suivant
+/// les connexions du graphe sans tenir compte de leurs exigences.
+///
+/// # Arguments
+/// * `zones` - Toutes les zones du jeu.
+/// * `depart` - Identifiant de la zone de départ.
+/// * `arrivee` - Identifiant de la zone à atteindre.
+///
+/// # Retour
+/// `None` si le PNJ est déjà arrivé ou si aucun chemin ne mène à la zone.
+fn prochaine_zone(zones: &[Zone], depart: u32, arrivee: u32) -> Option<u32> {
+    let voisines = |zone_id: u32| -> Vec<u32> {
+        zones.iter()
+            .find(|z| z.id as u32 == zone_id)
+            .map(|z| z.destinations().into_iter().map(u32::from).collect())
+            .unwrap_or_default()
+    };
+    // Chaque zone atteinte est associée au premier pas qui y mène
+    let mut premiers_pas: HashMap<u32, u32> = HashMap::new();
+    let mut a_traiter = VecDeque::from([depart]);
+    while let Some(zone_id) = a_traiter.pop_front() {
+        if zone_id == arrivee {
+            return premiers_pas.get(&zone_id).copied();
+        }
+        for voisine in voisines(zone_id) {
+            if voisine != depart && !premiers_pas.contains_key(&voisine) {
+                let premier_pas = premiers_pas.get(&zone_id).copied().unwrap_or(voisine);
+                premiers_pas.insert(voisine, premier_pas);
+                a_traiter.push_back(voisine);
+            }
+        }
+    }
+    None
+}
+
+/// Issue d'une interaction avec un PNJ, utilisée pour faire réagir les autres PNJs de la zone.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResultatInteraction {
+    /// Rien de notable ne s'est produit.
+    Aucun,
+    /// Le joueur a attaqué le PNJ sans le tuer.
+    Attaque,
+    /// Le joueur a tué le PNJ.
+    Meurtre,
 }
 
 impl PNJ {
@@ -491,9 +980,9 @@ impl PNJ {
     ) -> io::Result<Self> {
         let prochain_id = PNJ::prochain_id_pnj("src/json/pnj.json")?;
         let inventaire = PNJ::choisir_objets_inventaire()?;
-        let parties_du_corps = creer_parties_du_corps();
-
         let mut rng: ThreadRng = rand::rng();
+        let attributs = Attributs::aleatoires(&mut rng);
+        let parties_du_corps = creer_parties_du_corps(attributs.endurance);
         let valeur = rng.random_range(80..120);
         let valeur2 = rng.random_range(50..200); // Plus d'argent pour les marchands
 
@@ -506,13 +995,24 @@ impl PNJ {
             parties_du_corps,
             argent: valeur2,
             est_vivant: true,
+            progression: Progression::default(),
+            attributs,
         };
 
         let pnj = PNJ {
+            stock: personnage.inventaire.objets.clone(),
             personnage,
             dialogues,
             zone_id,
             multiplicateur_prix,
+            dernier_reapprovisionnement: Utc::now(),
+            reputation: 0,
+            remise: 0.0,
+            joueur_enfui: false,
+            arbre_dialogue: None,
+            deplacement: Deplacement::Fixe,
+            agressivite: Agressivite::Pacifique,
+            garde: false,
         };
 
         pnj.sauvegarder_pnj("src/json/pnj.json")?;
@@ -525,7 +1025,8 @@ impl PNJ {
     /// Affiche tous les objets disponibles, et demande à l'utilisateur de saisir
     /// les objets et quantités sous le format `id:quantité`.
     ///
-    /// La somme des quantités ne peut pas dépasser la capacité maximale de l'inventaire (10).
+    /// Les objets doivent tenir dans les 10 emplacements de l'inventaire, en respectant
+    /// le nombre maximal d'exemplaires par emplacement de chaque objet.
     ///
     /// # Exemple d'entrée utilisateur
     ///
@@ -553,7 +1054,7 @@ impl PNJ {
             io::stdin().read_line(&mut input)?;
 
             let choix: Vec<&str> = input.trim().split(',').collect();
-            let mut total_quantite = 0;
+            let mut plein = false;
 
             for choix_item in &choix {
                 let parts: Vec<&str> = choix_item.split(':').map(|s| s.trim()).collect();
@@ -561,7 +1062,9 @@ impl PNJ {
                     if let Ok(objet_id) = parts[0].parse::<u8>() {
                         if let Ok(quantite) = parts[1].parse::<u8>() {
                             if objets_disponibles.contains_key(&objet_id) {
-                                total_quantite += quantite;
+                                if inventaire.ajouter_objets(objet_id, quantite).is_err() {
+                                    plein = true;
+                                }
                             } else {
                                 println!("Objet avec l'ID {} non trouvé.", objet_id);
                             }
@@ -576,26 +1079,11 @@ impl PNJ {
                 }
             }
 
-            if total_quantite <= 10 {
-                for choix_item in &choix {
-                    let parts: Vec<&str> = choix_item.split(':').map(|s| s.trim()).collect();
-                    if parts.len() == 2 {
-                        if let Ok(objet_id) = parts[0].parse::<u8>() {
-                            if let Ok(quantite) = parts[1].parse::<u8>() {
-                                if objets_disponibles.contains_key(&objet_id) {
-                                    for _ in 0..quantite {
-                                        inventaire.ajouter_objet(objet_id);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+            if !plein {
                 break;
-            } else {
-                println!("La somme des quantités d'objets ne doit pas dépasser 10. Veuillez réessayer.");
-                inventaire.objets.clear(); // Vider l'inventaire pour réessayer
             }
+            println!("L'inventaire du marchand est limité à {} emplacements. Veuillez réessayer.", inventaire.taille);
+            inventaire.objets.clear(); // Vider l'inventaire pour réessayer
         }
 
         Ok(inventaire)
@@ -614,10 +1102,7 @@ impl PNJ {
     ///
     /// Retourne une erreur si l'écriture dans le fichier échoue.
     pub fn sauvegarder_pnj(&self, fichier: &str) -> io::Result<()> {
-        let mut pnjs = match Self::lire_pnjs_json(fichier)? {
-            Some(pnjs) => pnjs,
-            None => vec![],
-        };
+        let mut pnjs = Self::lire_pnjs_json(fichier)?.unwrap_or_default();
         
         pnjs.push(self.clone());
         let json = serde_json::to_string_pretty(&pnjs)?;
@@ -638,7 +1123,8 @@ impl PNJ {
     ///
     /// # Erreurs
     ///
-    /// Retourne une erreur si la lecture du fichier échoue.
+    /// Retourne une erreur si la lecture du fichier échoue ou si l'arbre de dialogue
+    /// d'un PNJ référence un nœud inexistant.
     fn lire_pnjs_json(fichier: &str) -> io::Result<Option<Vec<PNJ>>> {
         let mut file = match File::open(fichier) {
             Ok(file) => file,
@@ -652,8 +1138,18 @@ impl PNJ {
             return Ok(None);
         }
         
-        match serde_json::from_str(&contenu) {
-            Ok(pnjs) => Ok(Some(pnjs)),
+        match serde_json::from_str::<Vec<PNJ>>(&contenu) {
+            Ok(pnjs) => {
+                for pnj in pnjs.iter() {
+                    if let Some(Err(erreur)) = pnj.arbre_dialogue.as_ref().map(|a| a.verifier()) {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("Dialogue invalide pour {} : {}", pnj.personnage.nom, erreur),
+                        ));
+                    }
+                }
+                Ok(Some(pnjs))
+            }
             Err(_) => Ok(None),
         }
     }
@@ -767,8 +1263,9 @@ impl PNJ {
 
         for (id, (nom, description, dialogues, zone_id, multiplicateur_prix)) in pnjs_test.into_iter().enumerate() {
             let inventaire = Inventaire { taille: 10, objets: vec![] };
-            let parties_du_corps = creer_parties_du_corps();
             let mut rng: ThreadRng = rand::rng();
+            let attributs = Attributs::aleatoires(&mut rng);
+            let parties_du_corps = creer_parties_du_corps(attributs.endurance);
             let valeur = rng.random_range(100..140); // plus de force pour les marchands
             let valeur2 = rng.random_range(100..300); // Plus d'argent pour les marchands
 
@@ -781,6 +1278,8 @@ impl PNJ {
                 parties_du_corps,
                 argent: valeur2,
                 est_vivant: true,
+                progression: Progression::default(),
+                attributs,
             };
 
             let pnj = PNJ {
@@ -788,6 +1287,15 @@ impl PNJ {
                 dialogues,
                 zone_id,
                 multiplicateur_prix,
+                stock: vec![],
+                dernier_reapprovisionnement: Utc::now(),
+                reputation: 0,
+                remise: 0.0,
+                joueur_enfui: false,
+                arbre_dialogue: None,
+                deplacement: Deplacement::Fixe,
+                agressivite: Agressivite::Pacifique,
+                garde: false,
             };
 
             pnjs.push(pnj);
@@ -801,153 +1309,780 @@ impl PNJ {
         Ok(())
     }
 
-    /// Gère l’interaction entre le joueur et ce PNJ.
+    /// Gère l’interaction entre le joueur et ce PNJ en déroulant son arbre de dialogue.
     ///
-    /// Propose au joueur plusieurs choix : combattre, voir l’inventaire, ou quitter.
+    /// Le PNJ utilise son `arbre_dialogue` s’il en a un, sinon un arbre par défaut
+    /// construit à partir de ses `dialogues` (discuter, commercer, attaquer, partir).
+    /// Seules les réponses dont les conditions sont remplies sont proposées.
     ///
     /// # Arguments
     /// * `joueur` - Référence mutable au personnage joueur.
     /// * `zones` - Référence mutable à la liste des zones du jeu.
     /// * `current_zone_index` - Indice de la zone actuelle dans laquelle se trouve le joueur.
-    pub fn interagir(&mut self, joueur: &mut Personnage, zones: &mut Vec<Zone>, current_zone_index: usize) {
-        println!("Vous rencontrez {}. Que voulez-vous faire ?", self.personnage.nom);
-        println!("1. Combattre");
-        println!("2. Voir l'inventaire");
-        println!("3. Quitter");
-
-        let mut choix = String::new();
-        io::stdin().read_line(&mut choix).expect("Erreur de lecture !");
+    ///
+    /// # Retour
+    /// Un `ResultatInteraction` indiquant si le joueur a attaqué ou tué le PNJ,
+    /// pour faire réagir les témoins avec `reagir_violence`.
+    pub fn interagir(&mut self, joueur: &mut Personnage, zones: &mut [Zone], current_zone_index: usize) -> ResultatInteraction {
+        self.reapprovisionner(Utc::now());
+        let arbre = self.arbre_dialogue.clone()
+            .unwrap_or_else(|| ArbreDialogue::par_defaut(&self.dialogues));
+        let mut id_noeud = arbre.depart.clone();
+        println!("Vous rencontrez {}.", self.personnage.nom);
 
-        match choix.trim() {
-            "1" => {
-                println!("Vous avez choisi de combattre !");
-                let resultat = crate::combat::combattre(
-                    joueur.clone(),
-                    self.personnage.clone(),
-                    &zones[current_zone_index],
-                    &crate::personnage::PNJ::charger_pnj("src/json/pnj.json").unwrap_or_default()
-                );
-                if resultat.etat_final_joueur.est_vivant {
-                    *joueur = resultat.etat_final_joueur;
-                    println!("Vous avez gagné le combat contre le PNJ !");
-                    // Drop de l'inventaire du PNJ
-                    for objet in &self.personnage.inventaire.objets {
-                        for _ in 0..objet.nombre {
-                            zones[current_zone_index].objet_zone.ajouter_objet(objet.objet_id);
-                        }
-                    }
-                    self.personnage.inventaire.objets.clear();
-                    self.personnage.est_vivant = false;
-                    // Récupération de l'argent
-                    joueur.ajouter_argent(self.personnage.argent);
-                    println!("Vous ramassez {} d'argent sur le PNJ !", self.personnage.argent);
-                    self.personnage.argent = 0;
-                } else {
-                    *joueur = resultat.etat_final_joueur;
-                    joueur.est_vivant = false;
-                    println!("Vous avez perdu le combat contre le PNJ...");
+        loop {
+            let noeud = match arbre.noeud(&id_noeud) {
+                Some(noeud) => noeud,
+                None => {
+                    println!("{} ne sait plus quoi dire...", self.personnage.nom);
+                    return ResultatInteraction::Aucun;
                 }
+            };
+            let disponibles: Vec<&ChoixDialogue> = noeud.choix.iter()
+                .filter(|c| c.est_disponible(joueur, self.reputation))
+                .collect();
+
+            let mut message = format!("{} : « {} »\n", self.personnage.nom, noeud.texte);
+            if disponibles.is_empty() {
+                println!("{}", message);
+                return ResultatInteraction::Aucun;
             }
-            "2" => {
-                self.afficher_inventaire();
-                self.acheter_objet(joueur);
+            for (index, choix) in disponibles.iter().enumerate() {
+                message.push_str(&format!("  {}. {}\n", index + 1, choix.texte));
             }
-            "3" => {
+            let choix_possibles: Vec<String> = (1..=disponibles.len()).map(|i| i.to_string()).collect();
+            let choix = crate::affichage::faire_choix(&message, &choix_possibles);
+            if choix == "q" {
                 println!("Vous quittez l'interaction avec le PNJ.");
+                return ResultatInteraction::Aucun;
             }
-            _ => println!("Choix invalide !"),
-        }
-    }
-
+            let choix_dialogue = disponibles[choix.parse::<usize>().unwrap() - 1].clone();
 
-    /// Permet au joueur d’acheter un objet dans l’inventaire du PNJ.
-    ///
-    /// Vérifie la quantité disponible, l’argent du joueur, et la place dans son inventaire.
-    fn afficher_inventaire(&self) {
-        println!("Inventaire de {}:", self.personnage.nom);
-        for (index, objet) in self.personnage.inventaire.objets.iter().enumerate() {
-            if let Some(o) = OBJETS_DISPONIBLES.read().unwrap().get(&objet.objet_id) {
-                println!("{}: {} (x{}) - Prix: {} /unité",
-                         index + 1,
-                         o.nom,
-                         objet.nombre,
-                         self.calculer_prix_vente(o.prix));
+            // La réponse échoue sans rien appliquer si le joueur ne peut pas payer
+            if let Err(erreur) = choix_dialogue.effets.iter().try_for_each(|e| e.verifier(joueur)) {
+                println!("{}", erreur);
+                continue;
+            }
+            for effet in &choix_dialogue.effets {
+                if let Some(resultat) = self.appliquer_effet(effet, joueur, zones, current_zone_index) {
+                    return resultat;
+                }
+            }
+            match choix_dialogue.suivant {
+                Some(suivant) => id_noeud = suivant,
+                None => {
+                    println!("Vous quittez l'interaction avec le PNJ.");
+                    return ResultatInteraction::Aucun;
+                }
             }
         }
     }
 
-    /// Calcule le prix de vente d’un objet en appliquant le multiplicateur de prix du PNJ.
-    ///
-    /// # Arguments
-    /// * `prix_base` - Prix de base de l'objet.
+    /// Applique un effet de dialogue.
     ///
     /// # Retour
-    /// Le prix ajusté selon le multiplicateur du PNJ.
-    fn acheter_objet(&mut self, joueur: &mut Personnage) {
-        println!("Vous avez {} d'argent.", joueur.argent);
-        println!("Entrez le numéro de l'objet que vous souhaitez acheter ou 'q' pour quitter :");
-
-        let mut choix = String::new();
-        io::stdin().read_line(&mut choix).expect("Erreur de lecture !");
-
-        if choix.trim().eq_ignore_ascii_case("q") {
-            println!("Vous quittez l'interaction avec le PNJ.");
-            return;
+    /// `Some(resultat)` si l’effet met fin à la conversation (combat, ou objet demandé
+    /// que le joueur ne possède pas), `None` sinon.
+    fn appliquer_effet(&mut self, effet: &Effet, joueur: &mut Personnage, zones: &mut [Zone], current_zone_index: usize) -> Option<ResultatInteraction> {
+        let nom_objet = |id: &u8| OBJETS_DISPONIBLES.read().unwrap()
+            .get(id)
+            .map(|o| o.nom.clone())
+            .unwrap_or_else(|| format!("ID {}", id));
+        match effet {
+            Effet::DonnerObjet { objet_id, nombre } => {
+                if joueur.inventaire.ajouter_objets(*objet_id, *nombre).is_ok() {
+                    println!("{} vous donne {} x{}.", self.personnage.nom, nom_objet(objet_id), nombre);
+                } else {
+                    println!("Votre inventaire est plein, {} garde {}.", self.personnage.nom, nom_objet(objet_id));
+                }
+            }
+            Effet::PrendreObjet { objet_id, nombre } => {
+                if let Err(erreur) = effet.verifier(joueur) {
+                    println!("{}", erreur);
+                    return Some(ResultatInteraction::Aucun);
+                }
+                for _ in 0..*nombre {
+                    joueur.inventaire.retirer_par_id(*objet_id);
+                }
+                println!("Vous donnez {} x{} à {}.", nom_objet(objet_id), nombre, self.personnage.nom);
+            }
+            Effet::DonnerArgent { montant } => {
+                joueur.ajouter_argent(*montant);
+                println!("{} vous donne {} pièces.", self.personnage.nom, montant);
+            }
+            Effet::PrendreArgent { montant } => {
+                if joueur.argent >= *montant {
+                    joueur.retirer_argent(*montant);
+                    self.personnage.argent += montant;
+                    println!("Vous donnez {} pièces à {}.", montant, self.personnage.nom);
+                } else {
+                    println!("Vous n'avez pas assez d'argent !");
+                }
+            }
+            Effet::OuvrirZone { zone_id } => {
+                if let Some(zone) = zones.iter_mut().find(|z| z.id == *zone_id) {
+                    zone.ouvert = true;
+                    joueur.progression.ouvrir_zone(zone.id);
+                    println!("L'accès à la zone {} vous est désormais ouvert !", zone.nom);
+                }
+            }
+            Effet::ModifierPrix { multiplicateur } => self.multiplicateur_prix = *multiplicateur,
+            Effet::ModifierReputation { variation } => self.modifier_reputation(*variation),
+            Effet::PoserDrapeau { nom } => joueur.progression.poser_drapeau(nom),
+            Effet::DonnerQuete { quete_id } => {
+                if let Err(erreur) = crate::quete::accepter_quete(joueur, *quete_id) {
+                    println!("{}", erreur);
+                }
+            }
+            Effet::RendreQuete { quete_id } => {
+                if let Err(erreur) = crate::quete::rendre_quete(joueur, zones, *quete_id) {
+                    println!("{}", erreur);
+                }
+            }
+            Effet::Commercer => {
+                if self.refuse_commerce() {
+                    println!("{} refuse de commercer avec vous !", self.personnage.nom);
+                } else {
+                    self.commercer(joueur);
+                }
+            }
+            Effet::Combattre => {
+                // Attaquer un PNJ est un crime que les gardes feront payer
+                joueur.progression.infamie += 1;
+                return Some(self.combattre_joueur(joueur, zones, current_zone_index));
+            }
         }
+        None
+    }
 
-        if let Ok(index) = choix.trim().parse::<usize>() {
-            if index > 0 && index <= self.personnage.inventaire.objets.len() {
-                let objet_inv = self.personnage.inventaire.objets[index - 1].clone();
-                if let Some(objet) = OBJETS_DISPONIBLES.read().unwrap().get(&objet_inv.objet_id) {
-                    println!("Combien voulez-vous acheter de {} ?", objet.nom);
-                    let mut quantite = String::new();
-                    io::stdin().read_line(&mut quantite).expect("Erreur de lecture !");
-
-                    if let Ok(quantite) = quantite.trim().parse::<u8>() {
-                        let prix_total = self.calculer_prix_vente(objet.prix) * quantite as u32;
-                        let total_objet = joueur.inventaire.objets.iter().map(|o| o.nombre).sum::<u8>() + quantite;
-                        if quantite <= objet_inv.nombre && joueur.argent >= prix_total && total_objet <= joueur.inventaire.taille {
-                            joueur.retirer_argent(prix_total);
-                            self.personnage.argent += prix_total;
-
-                            // Ajouter l'objet à l'inventaire du joueur
-                            for _ in 0..quantite {
-                                joueur.inventaire.ajouter_objet(objet_inv.objet_id);
-                            }
-
-                            // Retirer l'objet de l'inventaire du PNJ
-                            self.personnage.inventaire.objets[index - 1].nombre -= quantite;
-                            if self.personnage.inventaire.objets[index - 1].nombre == 0 {
-                                self.personnage.inventaire.objets.remove(index - 1);
-                            }
-
-                            println!("Achat réussi !");
-                        } else {
-                            println!("Quantité invalide ou pas assez d'argent !");
-                        }
-                    }
+    /// Lance un combat entre le joueur et ce PNJ.
+    ///
+    /// Si le joueur gagne, le PNJ meurt, son inventaire tombe au sol et son argent
+    /// revient au joueur.
+    fn combattre_joueur(&mut self, joueur: &mut Personnage, zones: &mut [Zone], current_zone_index: usize) -> ResultatInteraction {
+        println!("Vous avez choisi de combattre !");
+        self.reputation = REPUTATION_MIN;
+        let resultat = crate::combat::combattre(
+            joueur.clone(),
+            self.personnage.clone(),
+            &zones[current_zone_index],
+            &crate::personnage::PNJ::charger_pnj("src/json/pnj.json").unwrap_or_default()
+        );
+        if resultat.etat_final_joueur.est_vivant {
+            *joueur = resultat.etat_final_joueur;
+            println!("Vous avez gagné le combat contre le PNJ !");
+            // Drop de l'inventaire du PNJ
+            for objet in &self.personnage.inventaire.objets {
+                if zones[current_zone_index].objet_zone.ajouter_exemplaire(objet.clone()).is_err() {
+                    println!("Il n'y a plus de place au sol, {} objet(s) d'ID {} sont perdus.", objet.nombre, objet.objet_id);
                 }
-            } else {
-                println!("Numéro d'objet invalide !");
             }
+            self.personnage.inventaire.objets.clear();
+            self.personnage.est_vivant = false;
+            joueur.progression.pnjs_tues.push(self.personnage.id);
+            // Récupération de l'argent
+            joueur.ajouter_argent(self.personnage.argent);
+            println!("Vous ramassez {} d'argent sur le PNJ !", self.personnage.argent);
+            self.personnage.argent = 0;
+            joueur.gagner_experience(COURBE_PROGRESSION.experience_combat(self.personnage.force));
+            ResultatInteraction::Meurtre
         } else {
-            println!("Entrée invalide !");
+            *joueur = resultat.etat_final_joueur;
+            joueur.est_vivant = false;
+            println!("Vous avez perdu le combat contre le PNJ...");
+            ResultatInteraction::Attaque
         }
     }
 
-    pub fn calculer_prix_vente(&self, prix_base: u32) -> u32 {
-        ((prix_base as f32) * self.multiplicateur_prix) as u32
+    /// Indique si le PNJ attaque le joueur de lui-même : un garde quand le joueur a
+    /// commis des crimes, un PNJ agressif toujours, un PNJ territorial quand il
+    /// déteste le joueur.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur.
+    pub fn est_hostile(&self, joueur: &Personnage) -> bool {
+        if !self.personnage.est_vivant {
+            return false;
+        }
+        if self.garde && joueur.progression.infamie > 0 {
+            return true;
+        }
+        match self.agressivite {
+            Agressivite::Pacifique => false,
+            Agressivite::Territorial => self.refuse_commerce(),
+            Agressivite::Agressif => true,
+        }
     }
-}
 
-// Affichage personnalisé pour les PNJ
-impl fmt::Display for PNJ {
+    /// Fait agir le premier PNJ hostile de la zone du joueur.
+    ///
+    /// Un garde propose de payer une amende ; le joueur peut aussi tenter de fuir
+    /// (il reste dans la zone mais échappe à l'attaque, le PNJ ne revient à la charge
+    /// qu'après qu'ils se sont séparés) ou se battre. Se défendre n'est pas un crime,
+    /// mais les témoins réagissent au combat.
+    ///
+    /// # Arguments
+    /// * `pnjs` - Tous les PNJs du jeu.
+    /// * `joueur` - Le personnage du joueur.
+    /// * `zones` - Toutes les zones du jeu.
+    /// * `current_zone_index` - Index de la zone du joueur.
+    pub fn affronter_hostiles(pnjs: &mut [PNJ], joueur: &mut Personnage, zones: &mut [Zone], current_zone_index: usize) {
+        let Some(index) = PNJ::premier_hostile(pnjs, joueur, zones[current_zone_index].id as u32) else {
+            return;
+        };
+        let nom = pnjs[index].personnage.nom.clone();
+        let mut options = vec!["combattre".to_string(), "fuir".to_string()];
+        let message = if pnjs[index].garde && joueur.progression.infamie > 0 {
+            options.push("payer".to_string());
+            format!("🛡️ {} vous arrête : « Vos crimes vous coûteront {} pièces. » (payer/combattre/fuir)",
+                nom, AMENDE_PAR_CRIME * joueur.progression.infamie)
+        } else {
+            format!("⚔️ {} vous attaque ! (combattre/fuir)", nom)
+        };
+        match crate::affichage::faire_choix(&message, &options).as_str() {
+            "payer" => {
+                let amende = AMENDE_PAR_CRIME * joueur.progression.infamie;
+                if joueur.argent >= amende {
+                    joueur.retirer_argent(amende);
+                    joueur.progression.infamie = 0;
+                    println!("Vous payez {} pièces d'amende, {} vous laisse partir.", amende, nom);
+                    return;
+                }
+                println!("Vous n'avez pas de quoi payer, {} dégaine son arme !", nom);
+            }
+            "fuir" => {
+                if rand::rng().random_bool(joueur.chance_fuite(&pnjs[index].personnage)) {
+                    println!("🏃 Vous échappez à {} pour le moment.", nom);
+                    pnjs[index].joueur_enfui = true;
+                    return;
+                }
+                println!("Vous n'arrivez pas à fuir !");
+            }
+            _ => {}
+        }
+        let resultat = pnjs[index].combattre_joueur(joueur, zones, current_zone_index);
+        PNJ::reagir_violence(pnjs, index, &resultat);
+    }
+
+    /// Renvoie l'index du premier PNJ hostile de la zone auquel le joueur n'a pas
+    /// déjà échappé.
+    ///
+    /// Un PNJ oublie la fuite du joueur dès qu'ils ne sont plus dans la même zone.
+    ///
+    /// # Arguments
+    /// * `pnjs` - Tous les PNJs du jeu.
+    /// * `joueur` - Le personnage du joueur.
+    /// * `zone_id` - Identifiant de la zone du joueur.
+    fn premier_hostile(pnjs: &mut [PNJ], joueur: &Personnage, zone_id: u32) -> Option<usize> {
+        for pnj in pnjs.iter_mut().filter(|p| p.zone_id != zone_id) {
+            pnj.joueur_enfui = false;
+        }
+        pnjs.iter().position(|p| p.zone_id == zone_id && !p.joueur_enfui && p.est_hostile(joueur))
+    }
+
+    /// Marque comme morts les PNJs que le joueur a tués lors de ses parties précédentes.
+    ///
+    /// # Arguments
+    /// * `pnjs` - Tous les PNJs du jeu.
+    /// * `joueur` - Le personnage du joueur.
+    pub fn appliquer_morts(pnjs: &mut [PNJ], joueur: &Personnage) {
+        for pnj in pnjs.iter_mut().filter(|p| joueur.progression.pnjs_tues.contains(&p.personnage.id)) {
+            pnj.personnage.est_vivant = false;
+            pnj.personnage.inventaire.objets.clear();
+            pnj.personnage.argent = 0;
+        }
+    }
+
+    /// Restaure l'état des PNJs enregistré dans la progression du joueur : inventaire,
+    /// argent, date du dernier réapprovisionnement, réputation du joueur et position
+    /// dans le monde actuel.
+    ///
+    /// # Arguments
+    /// * `pnjs` - Tous les PNJs du jeu.
+    /// * `joueur` - Le personnage du joueur.
+    pub fn restaurer_etats(pnjs: &mut [PNJ], joueur: &Personnage) {
+        for pnj in pnjs.iter_mut() {
+            if let Some(etat) = joueur.progression.etats_pnjs.get(&pnj.personnage.id) {
+                pnj.personnage.inventaire = etat.inventaire.clone();
+                pnj.personnage.argent = etat.argent;
+                pnj.dernier_reapprovisionnement = etat.dernier_reapprovisionnement;
+                pnj.reputation = etat.reputation;
+            }
+            if let Some(&zone_id) = joueur.progression.positions_pnjs.get(&pnj.personnage.id) {
+                pnj.zone_id = zone_id;
+            }
+        }
+    }
+
+    /// Enregistre l'état des PNJs dans la progression du joueur, avant sa sauvegarde.
+    ///
+    /// # Arguments
+    /// * `pnjs` - Tous les PNJs du jeu.
+    /// * `joueur` - Le personnage du joueur.
+    pub fn memoriser_etats(pnjs: &[PNJ], joueur: &mut Personnage) {
+        for pnj in pnjs {
+            joueur.progression.etats_pnjs.insert(pnj.personnage.id, EtatPnj {
+                inventaire: pnj.personnage.inventaire.clone(),
+                argent: pnj.personnage.argent,
+                dernier_reapprovisionnement: pnj.dernier_reapprovisionnement,
+                reputation: pnj.reputation,
+            });
+            joueur.progression.positions_pnjs.insert(pnj.personnage.id, pnj.zone_id);
+        }
+    }
+
+    /// Renvoie la zone où l'emploi du temps place le PNJ à cette heure, ou `None`
+    /// s'il n'en a pas.
+    ///
+    /// Avant la première étape de la journée, le PNJ est encore à la dernière étape de la veille.
+    ///
+    /// # Arguments
+    /// * `heure` - L'heure de jeu actuelle.
+    pub fn zone_prevue(&self, heure: u8) -> Option<u32> {
+        let Deplacement::Horaire { etapes } = &self.deplacement else {
+            return None;
+        };
+        etapes.iter()
+            .filter(|e| e.heure <= heure)
+            .max_by_key(|e| e.heure)
+            .or_else(|| etapes.iter().max_by_key(|e| e.heure))
+            .map(|e| e.zone_id)
+    }
+
+    /// Vérifie que chaque étape de l'emploi du temps mène à une zone voisine de
+    /// l'étape précédente, la dernière étape de la journée précédant la première.
+    ///
+    /// # Arguments
+    /// * `zones` - Toutes les zones du jeu.
+    ///
+    /// # Retour
+    /// `Err` décrivant la première étape qui ne suit pas une connexion.
+    pub fn verifier_horaire(&self, zones: &[Zone]) -> Result<(), String> {
+        let Deplacement::Horaire { etapes } = &self.deplacement else {
+            return Ok(());
+        };
+        let mut etapes: Vec<&EtapeHoraire> = etapes.iter().collect();
+        etapes.sort_by_key(|e| e.heure);
+        for (index, etape) in etapes.iter().enumerate() {
+            let precedente = etapes[(index + etapes.len() - 1) % etapes.len()];
+            let voisines = zones.iter()
+                .find(|z| z.id as u32 == precedente.zone_id)
+                .map(Zone::destinations)
+                .unwrap_or_default();
+            if precedente.zone_id != etape.zone_id && !voisines.iter().any(|&v| v as u32 == etape.zone_id) {
+                return Err(format!("Emploi du temps de {} : la zone {} ne mène pas à la zone {} ({}h)",
+                    self.personnage.nom, precedente.zone_id, etape.zone_id, etape.heure));
+            }
+        }
+        Ok(())
+    }
+
+    /// Déplace les PNJs vivants selon leur emploi du temps ou leur errance.
+    ///
+    /// Un PNJ ne passe que d'une zone à une zone voisine : loin de la zone prévue
+    /// par son emploi du temps, il fait un pas par appel sur le plus court chemin.
+    ///
+    /// # Arguments
+    /// * `pnjs` - Tous les PNJs du jeu.
+    /// * `zones` - Toutes les zones, pour trouver les zones voisines.
+    /// * `heure` - L'heure de jeu actuelle.
+    ///
+    /// # Retour
+    /// L'index de chaque PNJ déplacé avec l'identifiant de la zone qu'il a quittée.
+    pub fn deplacer_pnjs(pnjs: &mut [PNJ], zones: &[Zone], heure: u8) -> Vec<(usize, u32)> {
+        let mut rng = rand::rng();
+        let mut deplacements = Vec::new();
+        for (index, pnj) in pnjs.iter_mut().enumerate() {
+            if !pnj.personnage.est_vivant {
+                continue;
+            }
+            let destination = match &pnj.deplacement {
+                Deplacement::Fixe => None,
+                // Le PNJ rejoint la zone prévue de zone voisine en zone voisine
+                Deplacement::Horaire { .. } => pnj.zone_prevue(heure)
+                    .and_then(|cible| prochaine_zone(zones, pnj.zone_id, cible)),
+                Deplacement::Errant { chance } => {
+                    if rng.random_bool(chance.clamp(0.0, 1.0)) {
+                        let voisines: Vec<u32> = zones.iter()
+                            .find(|z| z.id as u32 == pnj.zone_id)
+                            .map(|z| z.connection.iter().filter_map(|c| c.id_dest.parse().ok()).collect())
+                            .unwrap_or_default();
+                        voisines.choose(&mut rng).copied()
+                    } else {
+                        None
+                    }
+                }
+            };
+            if let Some(zone_id) = destination.filter(|z| *z != pnj.zone_id) {
+                deplacements.push((index, pnj.zone_id));
+                pnj.zone_id = zone_id;
+            }
+        }
+        deplacements
+    }
+
+    /// Modifie la réputation du joueur auprès du PNJ, bornée entre
+    /// `REPUTATION_MIN` et `REPUTATION_MAX`.
+    ///
+    /// # Arguments
+    /// * `variation` - Points de réputation à ajouter (ou retirer si négatif).
+    pub fn modifier_reputation(&mut self, variation: i32) {
+        self.reputation = (self.reputation + variation).clamp(REPUTATION_MIN, REPUTATION_MAX);
+    }
+
+    /// Indique si le PNJ refuse de commercer avec le joueur à cause de sa réputation.
+    pub fn refuse_commerce(&self) -> bool {
+        self.reputation <= SEUIL_REFUS_COMMERCE
+    }
+
+    /// Fait réagir les PNJs vivants d'une zone aux violences du joueur envers l'un d'eux.
+    ///
+    /// Une attaque fait baisser la réputation des témoins ; un meurtre la fait tomber
+    /// au minimum, et les témoins refusent alors de commercer.
+    ///
+    /// # Arguments
+    /// * `pnjs` - Tous les PNJs du jeu.
+    /// * `victime` - Index du PNJ attaqué dans `pnjs`.
+    /// * `resultat` - Issue de l'interaction avec la victime.
+    pub fn reagir_violence(pnjs: &mut [PNJ], victime: usize, resultat: &ResultatInteraction) {
+        let zone_id = pnjs[victime].zone_id;
+        for (index, temoin) in pnjs.iter_mut().enumerate() {
+            if index == victime || temoin.zone_id != zone_id || !temoin.personnage.est_vivant {
+                continue;
+            }
+            match resultat {
+                ResultatInteraction::Attaque => temoin.modifier_reputation(-20),
+                ResultatInteraction::Meurtre => temoin.reputation = REPUTATION_MIN,
+                ResultatInteraction::Aucun => {}
+            }
+        }
+    }
+
+    /// Calcule la probabilité de réussir un marchandage avec ce PNJ.
+    ///
+    /// Elle dépend du charisme du joueur (qui amadoue le marchand)
+    /// et de sa réputation, et reste comprise entre 5 % et 90 %.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage qui marchande.
+    pub fn chance_marchandage(&self, joueur: &Personnage) -> f64 {
+        let charisme = joueur.attributs.charisme as f64 - ATTRIBUT_DEFAUT as f64;
+        let chance = 0.3 + charisme / 200.0 + self.reputation as f64 / 400.0;
+        chance.clamp(0.05, 0.9)
+    }
+
+    /// Remise accordée d'office au joueur s'il possède la zone du PNJ.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage qui commerce.
+    pub fn remise_proprietaire(&self, joueur: &Personnage) -> f32 {
+        if joueur.progression.zones_achetees.iter().any(|&z| z as u32 == self.zone_id) {
+            REMISE_PROPRIETAIRE
+        } else {
+            0.0
+        }
+    }
+
+    /// Tente de marchander avec le PNJ.
+    ///
+    /// En cas de réussite, le PNJ accorde une remise sur ses prix pour le reste de la visite.
+    /// En cas d'échec, il s'agace et la réputation du joueur baisse légèrement.
+    fn marchander(&mut self, joueur: &Personnage) {
+        if self.remise > self.remise_proprietaire(joueur) {
+            println!("{} vous a déjà fait un prix, n'en demandez pas trop !", self.personnage.nom);
+            return;
+        }
+        let mut rng = rand::rng();
+        if rng.random_bool(self.chance_marchandage(joueur)) {
+            self.remise += REMISE_MARCHANDAGE;
+            println!("{} accepte de baisser ses prix de {}% !", self.personnage.nom, (REMISE_MARCHANDAGE * 100.0) as u32);
+        } else {
+            self.modifier_reputation(-2);
+            println!("{} refuse sèchement de baisser ses prix.", self.personnage.nom);
+        }
+    }
+
+
+    /// Affiche la boutique du PNJ et laisse le joueur acheter ou vendre
+    /// jusqu'à ce qu'il quitte.
+    ///
+    /// # Arguments
+    /// * `joueur` - Référence mutable au personnage joueur.
+    fn commercer(&mut self, joueur: &mut Personnage) {
+        self.remise = self.remise_proprietaire(joueur);
+        if self.remise > 0.0 {
+            println!("{} traite avec le propriétaire des lieux : {}% de remise !", self.personnage.nom, (self.remise * 100.0) as u32);
+        }
+        loop {
+            println!("\n=== Boutique de {} ===", self.personnage.nom);
+            println!("Argent du marchand : {} | Votre argent : {}", self.personnage.argent, joueur.argent);
+            println!("Réputation : {}", self.reputation);
+            println!("1. Acheter");
+            println!("2. Vendre");
+            println!("3. Marchander");
+            println!("q. Quitter la boutique");
+
+            let mut choix = String::new();
+            io::stdin().read_line(&mut choix).expect("Erreur de lecture !");
+
+            match choix.trim() {
+                "1" => {
+                    self.afficher_inventaire(joueur);
+                    self.acheter_objet(joueur);
+                }
+                "2" => self.vendre_objet(joueur),
+                "3" => self.marchander(joueur),
+                "q" | "Q" => {
+                    println!("Vous quittez la boutique.");
+                    return;
+                }
+                _ => println!("Choix invalide !"),
+            }
+        }
+    }
+
+    /// Permet au joueur de vendre un objet de son inventaire au PNJ.
+    ///
+    /// Le prix de rachat est calculé par `calculer_prix_rachat`. La vente est refusée
+    /// si le marchand n’a pas assez d’argent ou plus de place dans son inventaire.
+    fn vendre_objet(&mut self, joueur: &mut Personnage) {
+        if joueur.inventaire.objets.is_empty() {
+            println!("Vous n'avez rien à vendre.");
+            return;
+        }
+
+        println!("Objets que {} peut vous racheter :", self.personnage.nom);
+        for (index, objet) in joueur.inventaire.objets.iter().enumerate() {
+            if let Some(o) = OBJETS_DISPONIBLES.read().unwrap().get(&objet.objet_id) {
+                println!("{}: {} (x{}) - Rachat: {} /unité",
+                         index + 1,
+                         objet.nom_affiche(o),
+                         objet.nombre,
+                         self.calculer_prix_rachat(o.prix, joueur.attributs.charisme));
+            }
+        }
+        println!("Entrez le numéro de l'objet que vous souhaitez vendre ou 'q' pour quitter :");
+
+        let mut choix = String::new();
+        io::stdin().read_line(&mut choix).expect("Erreur de lecture !");
+
+        if choix.trim().eq_ignore_ascii_case("q") {
+            return;
+        }
+
+        let index = match choix.trim().parse::<usize>() {
+            Ok(index) if index > 0 && index <= joueur.inventaire.objets.len() => index - 1,
+            _ => {
+                println!("Numéro d'objet invalide !");
+                return;
+            }
+        };
+        let objet_inv = joueur.inventaire.objets[index].clone();
+        let prix_unitaire = match OBJETS_DISPONIBLES.read().unwrap().get(&objet_inv.objet_id) {
+            Some(objet) => {
+                println!("Combien voulez-vous vendre de {} ? (max {})", objet.nom, objet_inv.nombre);
+                self.calculer_prix_rachat(objet.prix, joueur.attributs.charisme)
+            }
+            None => return,
+        };
+
+        let mut quantite = String::new();
+        io::stdin().read_line(&mut quantite).expect("Erreur de lecture !");
+        let quantite = match quantite.trim().parse::<u8>() {
+            Ok(q) if q > 0 && q <= objet_inv.nombre => q,
+            _ => {
+                println!("Quantité invalide !");
+                return;
+            }
+        };
+
+        match self.racheter(joueur, index, quantite, prix_unitaire) {
+            Ok(total) => {
+                self.modifier_reputation(1);
+                println!("Vente réussie ! Vous recevez {} pièces.", total);
+            }
+            Err(message) => println!("{}", message),
+        }
+    }
+
+    /// Transfère `quantite` exemplaires d’un emplacement de l’inventaire du joueur vers
+    /// le PNJ, avec leurs propriétés, et paie le joueur.
+    ///
+    /// # Retour
+    /// Le montant total payé, ou un message d’erreur si l'objet est un objet de quête
+    /// ou si le marchand n’a pas assez d’argent ou de place. Dans ce cas, rien n’est modifié.
+    fn racheter(&mut self, joueur: &mut Personnage, index: usize, quantite: u8, prix_unitaire: u32) -> Result<u32, String> {
+        let objet_id = joueur.inventaire.objets[index].objet_id;
+        if OBJETS_DISPONIBLES.read().unwrap().get(&objet_id).is_some_and(|o| !o.est_echangeable()) {
+            return Err("Un objet de quête ne peut pas être vendu.".to_string());
+        }
+        let total = prix_unitaire * quantite as u32;
+        if total > self.personnage.argent {
+            return Err(format!("{} n'a pas assez d'argent pour vous racheter cela.", self.personnage.nom));
+        }
+        let exemplaires = ObjetInventaire { nombre: quantite, ..joueur.inventaire.objets[index].clone() };
+        if self.personnage.inventaire.ajouter_exemplaire(exemplaires).is_err() {
+            return Err(format!("{} n'a plus de place pour ces objets.", self.personnage.nom));
+        }
+        joueur.inventaire.retirer_exemplaires(index, quantite);
+        self.personnage.argent -= total;
+        joueur.ajouter_argent(total);
+        Ok(total)
+    }
+
+    /// Réapprovisionne l’inventaire du PNJ à partir de son `stock` de référence.
+    ///
+    /// Chaque période de `INTERVALLE_REAPPROVISIONNEMENT` minutes écoulée depuis le
+    /// dernier réapprovisionnement rajoute un exemplaire de chaque objet en dessous
+    /// de sa quantité de référence, dans la limite de la place disponible.
+    ///
+    /// # Arguments
+    /// * `maintenant` - Date courante utilisée pour calculer les périodes écoulées.
+    pub fn reapprovisionner(&mut self, maintenant: DateTime<Utc>) {
+        let periodes = maintenant
+            .signed_duration_since(self.dernier_reapprovisionnement)
+            .num_minutes() / INTERVALLE_REAPPROVISIONNEMENT;
+        if periodes <= 0 {
+            return;
+        }
+
+        for reference in &self.stock {
+            let actuel: u32 = self.personnage.inventaire.objets.iter()
+                .filter(|o| o.objet_id == reference.objet_id)
+                .map(|o| o.nombre as u32)
+                .sum();
+            let manque = (reference.nombre as u32).saturating_sub(actuel);
+            let ajout = manque.min(periodes as u32);
+            for _ in 0..ajout {
+                if self.personnage.inventaire.ajouter_objet(reference.objet_id).is_err() {
+                    break;
+                }
+            }
+        }
+        self.dernier_reapprovisionnement += chrono::Duration::minutes(periodes * INTERVALLE_REAPPROVISIONNEMENT);
+    }
+
+    /// Affiche l’inventaire du PNJ avec le prix de vente de chaque objet pour ce joueur.
+    fn afficher_inventaire(&self, joueur: &Personnage) {
+        println!("Inventaire de {}:", self.personnage.nom);
+        for (index, objet) in self.personnage.inventaire.objets.iter().enumerate() {
+            if let Some(o) = OBJETS_DISPONIBLES.read().unwrap().get(&objet.objet_id) {
+                println!("{}: {} (x{}) - Prix: {} /unité",
+                         index + 1,
+                         objet.nom_affiche(o),
+                         objet.nombre,
+                         self.calculer_prix_vente(o.prix, joueur.attributs.charisme));
+            }
+        }
+    }
+
+    /// Permet au joueur d’acheter un objet dans l’inventaire du PNJ.
+    ///
+    /// Vérifie la quantité disponible, l’argent du joueur, et la place dans son inventaire.
+    fn acheter_objet(&mut self, joueur: &mut Personnage) {
+        println!("Vous avez {} d'argent.", joueur.argent);
+        println!("Entrez le numéro de l'objet que vous souhaitez acheter ou 'q' pour quitter :");
+
+        let mut choix = String::new();
+        io::stdin().read_line(&mut choix).expect("Erreur de lecture !");
+
+        if choix.trim().eq_ignore_ascii_case("q") {
+            println!("Vous quittez l'interaction avec le PNJ.");
+            return;
+        }
+
+        if let Ok(index) = choix.trim().parse::<usize>() {
+            if index > 0 && index <= self.personnage.inventaire.objets.len() {
+                let objet_inv = self.personnage.inventaire.objets[index - 1].clone();
+                if let Some(objet) = OBJETS_DISPONIBLES.read().unwrap().get(&objet_inv.objet_id) {
+                    println!("Combien voulez-vous acheter de {} ?", objet.nom);
+                    let mut quantite = String::new();
+                    io::stdin().read_line(&mut quantite).expect("Erreur de lecture !");
+
+                    if let Ok(quantite) = quantite.trim().parse::<u8>() {
+                        let prix_total = self.calculer_prix_vente(objet.prix, joueur.attributs.charisme) * quantite as u32;
+                        if !joueur.peut_porter(objet.poids * quantite as u32) {
+                            println!("C'est trop lourd, vous ne pouvez pas porter autant !");
+                        } else if quantite == 0 || quantite > objet_inv.nombre || joueur.argent < prix_total {
+                            println!("Quantité invalide ou pas assez d'argent !");
+                        } else if joueur.inventaire.ajouter_exemplaire(ObjetInventaire { nombre: quantite, ..objet_inv.clone() }).is_err() {
+                            println!("Pas assez de place dans votre inventaire !");
+                        } else {
+                            joueur.retirer_argent(prix_total);
+                            self.personnage.argent += prix_total;
+
+                            // Retirer l'objet de l'inventaire du PNJ
+                            self.personnage.inventaire.objets[index - 1].nombre -= quantite;
+                            if self.personnage.inventaire.objets[index - 1].nombre == 0 {
+                                self.personnage.inventaire.objets.remove(index - 1);
+                            }
+
+                            self.modifier_reputation(1);
+                            println!("Achat réussi !");
+                        }
+                    }
+                }
+            } else {
+                println!("Numéro d'objet invalide !");
+            }
+        } else {
+            println!("Entrée invalide !");
+        }
+    }
+
+    /// Calcule le prix de vente d’un objet en appliquant le multiplicateur de prix du PNJ.
+    ///
+    /// # Arguments
+    /// * `prix_base` - Prix de base de l'objet.
+    /// * `charisme` - Le charisme du joueur.
+    ///
+    /// # Retour
+    /// Le prix ajusté selon le multiplicateur du PNJ, la réputation et le charisme
+    /// du joueur, et une éventuelle remise obtenue en marchandant.
+    pub fn calculer_prix_vente(&self, prix_base: u32, charisme: u8) -> u32 {
+        let facteur_reputation = 1.0 - self.facteur_reputation() - facteur_charisme(charisme);
+        ((prix_base as f32) * self.multiplicateur_prix * facteur_reputation * (1.0 - self.remise)) as u32
+    }
+
+    /// Renvoie la part de variation des prix due à la réputation,
+    /// entre `-INFLUENCE_REPUTATION` et `INFLUENCE_REPUTATION`.
+    fn facteur_reputation(&self) -> f32 {
+        self.reputation as f32 / REPUTATION_MAX as f32 * INFLUENCE_REPUTATION
+    }
+
+    /// Calcule le prix auquel le PNJ rachète un objet au joueur.
+    ///
+    /// Le prix de base est réduit par `RATIO_RACHAT`, puis divisé par le multiplicateur
+    /// du PNJ : un marchand qui vend cher rachète aussi moins cher. Une bonne réputation
+    /// et un bon charisme augmentent le prix de rachat.
+    ///
+    /// # Arguments
+    /// * `prix_base` - Prix de base de l'objet.
+    /// * `charisme` - Le charisme du joueur.
+    pub fn calculer_prix_rachat(&self, prix_base: u32, charisme: u8) -> u32 {
+        if self.multiplicateur_prix <= 0.0 {
+            return 0;
+        }
+        let facteur_reputation = 1.0 + self.facteur_reputation() + facteur_charisme(charisme);
+        ((prix_base as f32) * RATIO_RACHAT / self.multiplicateur_prix * facteur_reputation) as u32
+    }
+}
+
+/// Renvoie la part de variation des prix due au charisme du joueur,
+/// entre `-INFLUENCE_CHARISME` et `INFLUENCE_CHARISME`.
+fn facteur_charisme(charisme: u8) -> f32 {
+    (charisme as f32 - ATTRIBUT_DEFAUT as f32) / ATTRIBUT_DEFAUT as f32 * INFLUENCE_CHARISME
+}
+
+// Affichage personnalisé pour les PNJ
+impl fmt::Display for PNJ {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "=== PNJ Marchand #{} ===", self.personnage.id)?;
         writeln!(f, "Nom         : {}", self.personnage.nom)?;
         writeln!(f, "Description : {}", self.personnage.description)?;
         writeln!(f, "Zone        : {}", self.zone_id)?;
         writeln!(f, "Multiplicateur prix : {:.1}", self.multiplicateur_prix)?;
+        writeln!(f, "Réputation  : {}", self.reputation)?;
         writeln!(f, "Argent      : {}", self.personnage.argent)?;
         writeln!(f, "Dialogues   :")?;
         for (i, dialogue) in self.dialogues.iter().enumerate() {
@@ -962,7 +2097,16 @@ impl fmt::Display for PNJ {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Mob {
     /// Le personnage associé au Mob.
+    #[serde(flatten)]
     pub personnage: Personnage,
+    /// Tendance du Mob à attaquer le joueur qui entre dans sa zone.
+    #[serde(default = "agressivite_mob")]
+    pub agressivite: Agressivite,
+}
+
+/// Agressivité des Mobs qui n'en déclarent pas.
+fn agressivite_mob() -> Agressivite {
+    Agressivite::Agressif
 }
 
 impl Mob {
@@ -983,9 +2127,9 @@ impl Mob {
     pub fn creer_mob(nom: &str, description: &str) -> io::Result<Self> {
         let prochain_id = Personnage::prochain_id("src/json/mob.json")?;
         let inventaire = Inventaire { taille: 10, objets: vec![] };
-        let parties_du_corps = creer_parties_du_corps();
-
         let mut rng: ThreadRng = rand::rng();
+        let attributs = Attributs::aleatoires(&mut rng);
+        let parties_du_corps = creer_parties_du_corps(attributs.endurance);
         let valeur = rng.random_range(80..120);
         let valeur2 = rng.random_range(0..20);
 
@@ -998,10 +2142,12 @@ impl Mob {
             parties_du_corps,
             argent: valeur2,
             est_vivant: true,
+            progression: Progression::default(),
+            attributs,
         };
 
         personnage.sauvegarder_json("src/json/mob.json")?;
-        Ok(Mob { personnage })
+        Ok(Mob { personnage, agressivite: agressivite_mob() })
     }
 
     /// Charge la liste des Mobs depuis un fichier JSON.
@@ -1015,14 +2161,14 @@ impl Mob {
     /// # Erreurs
     ///
     /// Retourne une erreur d'entrée/sortie si le fichier ne peut être lu ou parsé.
-    pub fn charger_mob(fichier: &str) -> io::Result<Vec<Personnage>> {
-        let mobs = Personnage::charger_depuis_json(fichier)?;
-        if mobs.is_empty() {
+    pub fn charger_mob(fichier: &str) -> io::Result<Vec<Mob>> {
+        if Personnage::charger_depuis_json(fichier)?.is_empty() {
             println!("Aucun Mob trouvé, création de Mobs de test...");
             Self::creer_mobs_test_direct()?;
-            return Personnage::charger_depuis_json(fichier);
         }
-        Ok(mobs)
+        let mut contenu = String::new();
+        File::open(fichier)?.read_to_string(&mut contenu)?;
+        Ok(serde_json::from_str(&contenu)?)
     }
 
     /// Crée directement plusieurs Mobs de test avec des valeurs prédéfinies.
@@ -1044,12 +2190,11 @@ impl Mob {
         ];
 
         let mut personnages = vec![];
-        let mut current_id = 1;
-
-        for (nom, description) in mobs_test {
+        for (current_id, (nom, description)) in (1..).zip(mobs_test) {
             let inventaire = Inventaire { taille: 10, objets: vec![] };
-            let parties_du_corps = creer_parties_du_corps();
             let mut rng: ThreadRng = rand::rng();
+            let attributs = Attributs::aleatoires(&mut rng);
+            let parties_du_corps = creer_parties_du_corps(attributs.endurance);
             let valeur = rng.random_range(80..120);
             let valeur2 = rng.random_range(0..20);
 
@@ -1062,10 +2207,11 @@ impl Mob {
                 parties_du_corps,
                 argent: valeur2,
                 est_vivant: true,
+                progression: Progression::default(),
+                attributs,
             };
 
             personnages.push(personnage);
-            current_id += 1;
         }
 
         let json = serde_json::to_string_pretty(&personnages)?;
@@ -1079,42 +2225,87 @@ impl Mob {
 }
 
 impl Joueur {
-    /// Crée un nouveau Joueur avec un nom et une description.
+    /// Crée un nouveau Joueur avec un nom, une description et une classe.
     ///
-    /// Le Joueur est initialisé avec des valeurs aléatoires pour la force et l'argent,
-    /// un inventaire vide et des parties du corps générées.
+    /// Le Joueur reçoit les statistiques, l'argent, l'équipement et la zone de départ
+    /// de sa classe, ainsi que les points qu'elle lui laisse à répartir.
     /// Le Joueur est sauvegardé dans le fichier JSON spécifié.
     ///
     /// # Arguments
     ///
     /// * `nom` - Nom du Joueur.
     /// * `description` - Description du Joueur.
+    /// * `classe` - La classe choisie.
     ///
     /// # Erreurs
     ///
     /// Retourne une erreur d'entrée/sortie si la sauvegarde échoue.
-    pub fn creer_joueur(nom: &str, description: &str) -> io::Result<Self> {
+    pub fn creer_joueur(nom: &str, description: &str, classe: &Classe) -> io::Result<Self> {
         let prochain_id = Personnage::prochain_id("src/json/personnage.json")?;
-        let inventaire = Inventaire { taille: 10, objets: vec![] };
-        let parties_du_corps = creer_parties_du_corps();
-
-        let mut rng: ThreadRng = rand::rng();
-        let valeur = rng.random_range(80..120);
-        let valeur2 = rng.random_range(0..20);
+        let personnage = Self::depuis_classe(prochain_id, nom, description, classe);
+        personnage.sauvegarder_json("src/json/personnage.json")?;
+        Ok(Joueur { personnage })
+    }
 
-        let personnage = Personnage {
-            id: prochain_id,
+    /// Construit le personnage d'un joueur à partir de sa classe.
+    ///
+    /// Les armes sont portées au bras droit et les équipements sur la tête ou le torse
+    /// quand la partie est libre ; les autres objets vont dans l'inventaire.
+    fn depuis_classe(id: u32, nom: &str, description: &str, classe: &Classe) -> Personnage {
+        let mut personnage = Personnage {
+            id,
             nom: nom.to_string(),
             description: description.to_string(),
-            force: valeur,
-            inventaire,
-            parties_du_corps,
-            argent: valeur2,
+            force: classe.force,
+            inventaire: Inventaire { taille: 10, objets: vec![] },
+            parties_du_corps: creer_parties_du_corps(classe.attributs.endurance),
+            argent: classe.argent,
             est_vivant: true,
+            progression: Progression::default(),
+            attributs: classe.attributs.clone(),
         };
+        personnage.progression.zone_actuelle = classe.zone_depart;
+        personnage.progression.points_a_repartir = classe.points_a_repartir;
+
+        for objet in &classe.equipement {
+            let nom_partie = match OBJETS_DISPONIBLES.read().unwrap().get(&objet.objet_id) {
+                Some(o) if o.est_arme() => Some("Bras droit"),
+                Some(o) if o.est_equipement() && o.est_pour_emplacement(crate::objet::Emplacement::Tete) => Some("Tête"),
+                Some(o) if o.est_equipement() => Some("Torse"),
+                _ => None,
+            };
+            let mut restant = objet.nombre;
+            if let Some(partie) = nom_partie.and_then(|n| personnage.parties_du_corps.iter_mut().find(|p| p.nom == n)) {
+                if partie.ajouter_equipement(objet.clone()).is_ok() {
+                    restant -= 1;
+                }
+            }
+            if restant > 0 && personnage.inventaire.ajouter_objets(objet.objet_id, restant).is_err() {
+                println!("⚠️ Inventaire plein, l'objet de départ d'ID {} est perdu.", objet.objet_id);
+            }
+        }
+        personnage
+    }
 
-        personnage.sauvegarder_json("src/json/personnage.json")?;
-        Ok(Joueur { personnage })
+    /// Vérifie qu'un nom de personnage n'est pas vide et n'est pas déjà pris.
+    ///
+    /// # Arguments
+    ///
+    /// * `nom` - Nom proposé.
+    /// * `existants` - Les personnages déjà créés.
+    ///
+    /// # Retour
+    ///
+    /// `Err` avec le motif du refus.
+    pub fn valider_nom(nom: &str, existants: &[Personnage]) -> Result<(), String> {
+        let nom = nom.trim();
+        if nom.is_empty() {
+            return Err("Le nom ne peut pas être vide".to_string());
+        }
+        if existants.iter().any(|p| p.nom.trim().to_lowercase() == nom.to_lowercase()) {
+            return Err(format!("Le nom « {} » est déjà pris", nom));
+        }
+        Ok(())
     }
 
     /// Charge la liste des Joueurs depuis un fichier JSON.
@@ -1147,12 +2338,11 @@ impl Joueur {
         ];
 
         let mut personnages = vec![];
-        let mut current_id = 1;
-
-        for (nom, description) in joueurs_test {
+        for (current_id, (nom, description)) in (1..).zip(joueurs_test) {
             let inventaire = Inventaire { taille: 10, objets: vec![] };
-            let parties_du_corps = creer_parties_du_corps();
             let mut rng: ThreadRng = rand::rng();
+            let attributs = Attributs::aleatoires(&mut rng);
+            let parties_du_corps = creer_parties_du_corps(attributs.endurance);
             let valeur = rng.random_range(80..120);
             let valeur2 = rng.random_range(0..20);
 
@@ -1165,10 +2355,11 @@ impl Joueur {
                 parties_du_corps,
                 argent: valeur2,
                 est_vivant: true,
+                progression: Progression::default(),
+                attributs,
             };
 
             personnages.push(personnage);
-            current_id += 1;
         }
 
         let json = serde_json::to_string_pretty(&personnages)?;
@@ -1183,6 +2374,7 @@ impl Joueur {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::outils_test::{catalogue_test, joueur_test, zone_test, HACHE, LANCE, PIERRE, RELIQUE, ROCHER, SABRE};
 
     #[test]
     fn test_partie_du_corps_new_et_etat() {
@@ -1196,7 +2388,7 @@ mod tests {
     #[test]
     fn test_subir_degats_et_mort() {
         let mut partie = PartieDuCorps::new("Bras".to_string(), 10);
-        let detruite = partie.subir_degats(15);
+        let detruite = partie.subir_degats(15, 1.0);
         assert!(detruite);
         assert!(partie.est_morte());
         assert_eq!(partie.vie_actuelle(), 0);
@@ -1213,6 +2405,8 @@ mod tests {
             parties_du_corps: vec![PartieDuCorps::new("Tête".to_string(), 10)],
             argent: 0,
             est_vivant: true,
+            progression: Progression::default(),
+            attributs: Attributs::default(),
         };
         let res = p.gerer_blessure("Tête", 15);
         assert!(matches!(res, ResultatBlessure::Mort));
@@ -1220,4 +2414,471 @@ mod tests {
         // let soigner = p.soigner_partie("Tête", 5);
         // assert!(!soigner); // ne peut pas soigner un mort
     }
+
+    #[test]
+    fn test_guerison_selon_endurance() {
+        let mut joueur = joueur_test(vec![], 1);
+        joueur.attributs.endurance = 100;
+        joueur.parties_du_corps = vec![PartieDuCorps::new("Bras".to_string(), 10)];
+        let delai = |j: &Personnage| (j.parties_du_corps[0].guerison - Utc::now()).num_seconds();
+
+        // Blessé à 50 % : 50 minutes, divisées par deux par l'endurance
+        joueur.gerer_blessure("Bras", 5);
+        assert!((1490..=1500).contains(&delai(&joueur)));
+        // Les coups qui ne fixent pas de nouveau délai ne réappliquent pas le facteur
+        let guerison = joueur.parties_du_corps[0].guerison;
+        joueur.gerer_blessure("Bras", 5);
+        joueur.gerer_blessure("Bras", 5);
+        assert!(joueur.parties_du_corps[0].est_morte());
+        assert_eq!(joueur.parties_du_corps[0].guerison, guerison);
+    }
+
+    #[test]
+    fn test_surcharge_poids() {
+        catalogue_test();
+        let mut p = joueur_test(vec![], 5);
+        p.force = 100;
+        p.parties_du_corps = vec![PartieDuCorps::new("Tête".to_string(), 10)];
+        assert_eq!(p.poids_max(), 10000);
+        assert!(!p.est_surcharge());
+        assert_eq!(p.force_effective(), 100);
+        for _ in 0..3 {
+            p.inventaire.ajouter_objet(ROCHER).unwrap();
+        }
+        assert!(p.est_surcharge());
+        assert_eq!(p.force_effective(), 75);
+        assert!(p.peut_porter(3000));
+        assert!(!p.peut_porter(4000));
+    }
+
+    fn pnj_test(argent: u32, multiplicateur_prix: f32) -> PNJ {
+        PNJ {
+            personnage: Personnage {
+                id: 1,
+                nom: "Marchand".to_string(),
+                description: "desc".to_string(),
+                force: 100,
+                inventaire: crate::inventaire::Inventaire { taille: 2, objets: vec![] },
+                parties_du_corps: creer_parties_du_corps(ATTRIBUT_DEFAUT),
+                argent,
+                est_vivant: true,
+                progression: Progression::default(),
+                attributs: Attributs::default(),
+            },
+            dialogues: vec![],
+            zone_id: 1,
+            multiplicateur_prix,
+            stock: vec![],
+            dernier_reapprovisionnement: Utc::now(),
+            reputation: 0,
+            remise: 0.0,
+            joueur_enfui: false,
+            arbre_dialogue: None,
+            deplacement: Deplacement::Fixe,
+            agressivite: Agressivite::Pacifique,
+            garde: false,
+        }
+    }
+
+    #[test]
+    fn test_vente_au_marchand() {
+        catalogue_test();
+        let mut pnj = pnj_test(50, 2.0);
+        assert_eq!(pnj.calculer_prix_rachat(100, ATTRIBUT_DEFAUT), 25);
+        let mut joueur = pnj_test(0, 1.0).personnage;
+        joueur.inventaire.ajouter_objets(PIERRE, 3).unwrap();
+
+        // Le marchand ne peut pas payer 3 x 25 pièces
+        assert!(pnj.racheter(&mut joueur, 0, 3, 25).is_err());
+        assert_eq!(joueur.inventaire.objets[0].nombre, 3);
+
+        assert_eq!(pnj.racheter(&mut joueur, 0, 2, 25), Ok(50));
+        assert_eq!(joueur.argent, 50);
+        assert_eq!(pnj.personnage.argent, 0);
+        assert_eq!(joueur.inventaire.objets[0].nombre, 1);
+        assert_eq!(pnj.personnage.inventaire.objets[0].nombre, 2);
+
+        joueur.inventaire.ajouter_objets(RELIQUE, 1).unwrap();
+        let index = joueur.inventaire.objets.iter().position(|o| o.objet_id == RELIQUE).unwrap();
+        assert!(pnj.racheter(&mut joueur, index, 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_avantages_proprietaire() {
+        let pnj = pnj_test(0, 1.0);
+        let mut joueur = pnj_test(0, 1.0).personnage;
+        joueur.parties_du_corps = vec![PartieDuCorps::new("Bras".to_string(), 10), PartieDuCorps::new("Jambe".to_string(), 10)];
+        assert_eq!(pnj.remise_proprietaire(&joueur), 0.0);
+        joueur.progression.acheter_zone(pnj.zone_id as u8);
+        assert_eq!(pnj.remise_proprietaire(&joueur), REMISE_PROPRIETAIRE);
+
+        joueur.gerer_blessure("Bras", 4);
+        joueur.gerer_blessure("Jambe", 10);
+        assert_eq!(joueur.se_reposer(), 1);
+        assert_eq!(joueur.parties_du_corps[0].vie_actuelle, 10);
+        assert!(joueur.parties_du_corps[1].est_morte());
+    }
+
+    #[test]
+    fn test_restaurer_vie() {
+        let mut joueur = pnj_test(0, 1.0).personnage;
+        joueur.parties_du_corps = vec![
+            PartieDuCorps::new("Bras".to_string(), 10),
+            PartieDuCorps::new("Jambe".to_string(), 20),
+            PartieDuCorps::new("Tête".to_string(), 10),
+        ];
+        joueur.gerer_blessure("Bras", 3);
+        joueur.gerer_blessure("Jambe", 15);
+        joueur.gerer_blessure("Tête", 10);
+        assert_eq!(joueur.restaurer_vie(5), 8);
+        assert!(joueur.parties_du_corps[0].est_saine());
+        assert_eq!(joueur.parties_du_corps[1].vie_actuelle, 10);
+        assert!(joueur.parties_du_corps[1].est_blessee());
+        assert!(joueur.parties_du_corps[2].est_morte());
+    }
+
+    #[test]
+    fn test_equiper_sans_perte() {
+        catalogue_test();
+        // Une ancienne pile de deux haches occupe l'unique emplacement de l'inventaire
+        let mut joueur = joueur_test(vec![ObjetInventaire { nombre: 2, objet_id: HACHE, instance: None }], 1);
+        joueur.parties_du_corps = vec![PartieDuCorps::new("Bras droit".to_string(), 10)];
+        joueur.parties_du_corps[0].ajouter_equipement(ObjetInventaire { nombre: 1, objet_id: SABRE, instance: None }).unwrap();
+
+        // Ni l'inventaire ni le sol ne peuvent accueillir le sabre : rien ne change
+        let mut sol = Inventaire { taille: 0, objets: vec![] };
+        assert!(joueur.equiper(0, 0, &mut sol).is_err());
+        assert_eq!(joueur.inventaire.objets[0].nombre, 2);
+        assert_eq!(joueur.parties_du_corps[0].equipement().objets[0].objet_id, SABRE);
+
+        sol.taille = 1;
+        assert_eq!(joueur.equiper(0, 0, &mut sol), Ok(true));
+        assert_eq!(joueur.inventaire.objets[0].nombre, 1);
+        assert_eq!(joueur.parties_du_corps[0].equipement().objets[0].objet_id, HACHE);
+        assert_eq!(sol.objets[0].objet_id, SABRE);
+    }
+
+    #[test]
+    fn test_reapprovisionnement() {
+        catalogue_test();
+        let mut pnj = pnj_test(0, 1.0);
+        pnj.stock = vec![ObjetInventaire { nombre: 3, objet_id: PIERRE, instance: None }];
+        let debut = pnj.dernier_reapprovisionnement;
+
+        pnj.reapprovisionner(debut + chrono::Duration::minutes(INTERVALLE_REAPPROVISIONNEMENT - 1));
+        assert!(pnj.personnage.inventaire.objets.is_empty());
+
+        pnj.reapprovisionner(debut + chrono::Duration::minutes(2 * INTERVALLE_REAPPROVISIONNEMENT));
+        assert_eq!(pnj.personnage.inventaire.objets[0].nombre, 2);
+
+        pnj.reapprovisionner(debut + chrono::Duration::minutes(10 * INTERVALLE_REAPPROVISIONNEMENT));
+        assert_eq!(pnj.personnage.inventaire.objets[0].nombre, 3);
+    }
+
+    #[test]
+    fn test_etats_des_pnjs_sauvegardes() {
+        catalogue_test();
+        let mut marchand = pnj_test(40, 1.0);
+        marchand.stock = vec![ObjetInventaire { nombre: 3, objet_id: PIERRE, instance: None }];
+        marchand.personnage.inventaire.ajouter_objet(PIERRE).unwrap();
+        let reapprovisionne = marchand.dernier_reapprovisionnement - chrono::Duration::minutes(5);
+        marchand.dernier_reapprovisionnement = reapprovisionne;
+        let mut joueur = joueur_test(vec![], 1);
+        PNJ::memoriser_etats(std::slice::from_ref(&marchand), &mut joueur);
+
+        // Au chargement suivant, pnj.json redonne un marchand neuf, remplacé par l'état sauvegardé
+        let joueur: Personnage = serde_json::from_str(&serde_json::to_string(&joueur).unwrap()).unwrap();
+        let mut pnjs = vec![pnj_test(0, 1.0)];
+        PNJ::restaurer_etats(&mut pnjs, &joueur);
+        assert_eq!(pnjs[0].personnage.argent, 40);
+        assert_eq!(pnjs[0].personnage.inventaire.objets[0].nombre, 1);
+        assert_eq!(pnjs[0].dernier_reapprovisionnement, reapprovisionne);
+        assert_eq!(pnjs[0].zone_id, 1);
+    }
+
+    #[test]
+    fn test_position_des_pnjs_sauvegardee() {
+        let mut pnjs = vec![pnj_test(0, 1.0)];
+        pnjs[0].zone_id = 3;
+        let mut joueur = joueur_test(vec![], 1);
+        PNJ::memoriser_etats(&pnjs, &mut joueur);
+        let joueur: Personnage = serde_json::from_str(&serde_json::to_string(&joueur).unwrap()).unwrap();
+
+        let mut pnjs = vec![pnj_test(0, 1.0)];
+        PNJ::restaurer_etats(&mut pnjs, &joueur);
+        assert_eq!(pnjs[0].zone_id, 3);
+    }
+
+    #[test]
+    fn test_reputation_propre_au_joueur() {
+        let mut pnjs = vec![pnj_test(0, 1.0)];
+        pnjs[0].modifier_reputation(30);
+        let mut joueur = joueur_test(vec![], 1);
+        PNJ::memoriser_etats(&pnjs, &mut joueur);
+        let sauvegarde = serde_json::to_string(&joueur).unwrap();
+
+        // Un autre joueur retrouve le PNJ tel que décrit dans pnj.json
+        let mut pnjs = vec![pnj_test(0, 1.0)];
+        PNJ::restaurer_etats(&mut pnjs, &joueur_test(vec![], 1));
+        assert_eq!(pnjs[0].reputation, 0);
+        // Le premier joueur retrouve sa réputation après rechargement
+        let joueur: Personnage = serde_json::from_str(&sauvegarde).unwrap();
+        PNJ::restaurer_etats(&mut pnjs, &joueur);
+        assert_eq!(pnjs[0].reputation, 30);
+        assert_eq!(pnjs[0].calculer_prix_vente(100, ATTRIBUT_DEFAUT), 94);
+    }
+
+    #[test]
+    fn test_prix_et_reputation() {
+        let mut pnj = pnj_test(0, 1.0);
+        assert_eq!(pnj.calculer_prix_vente(100, ATTRIBUT_DEFAUT), 100);
+        assert_eq!(pnj.calculer_prix_vente(100, 100), 90);
+        assert_eq!(pnj.calculer_prix_rachat(100, 0), 45);
+        pnj.modifier_reputation(500);
+        assert_eq!(pnj.reputation, REPUTATION_MAX);
+        assert_eq!(pnj.calculer_prix_vente(100, ATTRIBUT_DEFAUT), 80);
+        assert_eq!(pnj.calculer_prix_rachat(100, ATTRIBUT_DEFAUT), 60);
+        pnj.remise = REMISE_MARCHANDAGE;
+        assert_eq!(pnj.calculer_prix_vente(100, ATTRIBUT_DEFAUT), 72);
+        pnj.modifier_reputation(-500);
+        assert_eq!(pnj.reputation, REPUTATION_MIN);
+        assert!(pnj.refuse_commerce());
+    }
+
+    /// Crée les zones 1 à 4 reliées en ligne, dans les deux sens.
+    fn zones_en_ligne() -> Vec<Zone> {
+        let relier = |id: u8, voisines: &[u8]| Zone {
+            connection: voisines.iter()
+                .map(|v| crate::zone::Connexion { direction: format!("vers {}", v), id_dest: v.to_string(), exigences: vec![] })
+                .collect(),
+            ..zone_test(id)
+        };
+        vec![relier(1, &[2]), relier(2, &[1, 3]), relier(3, &[2, 4]), relier(4, &[3])]
+    }
+
+    #[test]
+    fn test_deplacement_des_pnjs() {
+        let zones = zones_en_ligne();
+        let mut pnjs = vec![pnj_test(0, 1.0), pnj_test(0, 1.0), pnj_test(0, 1.0)];
+        pnjs[0].deplacement = Deplacement::Horaire { etapes: vec![
+            EtapeHoraire { heure: 8, zone_id: 3 },
+            EtapeHoraire { heure: 18, zone_id: 4 },
+        ] };
+        assert_eq!(pnjs[0].zone_prevue(12), Some(3));
+        assert_eq!(pnjs[0].zone_prevue(20), Some(4));
+        assert_eq!(pnjs[0].zone_prevue(2), Some(4));
+        pnjs[1].deplacement = Deplacement::Errant { chance: 1.0 };
+        pnjs[2].deplacement = Deplacement::Errant { chance: 1.0 };
+        pnjs[2].personnage.id = 3;
+
+        // Le PNJ tué lors d'une partie précédente reste mort et ne bouge plus
+        let mut joueur = pnj_test(0, 1.0).personnage;
+        joueur.progression.pnjs_tues.push(3);
+        PNJ::appliquer_morts(&mut pnjs, &joueur);
+        assert!(!pnjs[2].personnage.est_vivant);
+
+        // La zone 3 est à deux pas : le PNJ passe d'abord par la zone 2
+        let deplacements = PNJ::deplacer_pnjs(&mut pnjs, &zones, 12);
+        assert_eq!(deplacements, vec![(0, 1), (1, 1)]);
+        assert_eq!(pnjs[0].zone_id, 2);
+        assert_eq!(pnjs[1].zone_id, 2);
+        assert_eq!(pnjs[2].zone_id, 1);
+        PNJ::deplacer_pnjs(&mut pnjs, &zones, 12);
+        assert_eq!(pnjs[0].zone_id, 3);
+        PNJ::deplacer_pnjs(&mut pnjs, &zones, 12);
+        assert_eq!(pnjs[0].zone_id, 3);
+        // Sans chemin vers la zone prévue, le PNJ reste où il est
+        PNJ::deplacer_pnjs(&mut pnjs, &zones[..2], 20);
+        assert_eq!(pnjs[0].zone_id, 3);
+    }
+
+    #[test]
+    fn test_emploi_du_temps_suit_les_connexions() {
+        let zones = zones_en_ligne();
+        let mut pnj = pnj_test(0, 1.0);
+        assert!(pnj.verifier_horaire(&zones).is_ok());
+        pnj.deplacement = Deplacement::Horaire { etapes: vec![
+            EtapeHoraire { heure: 18, zone_id: 3 },
+            EtapeHoraire { heure: 8, zone_id: 2 },
+        ] };
+        assert!(pnj.verifier_horaire(&zones).is_ok());
+        // De 4 à 2, puis de 2 à 4 la nuit : aucune connexion directe
+        pnj.deplacement = Deplacement::Horaire { etapes: vec![
+            EtapeHoraire { heure: 8, zone_id: 2 },
+            EtapeHoraire { heure: 18, zone_id: 4 },
+        ] };
+        assert_eq!(pnj.verifier_horaire(&zones), Err("Emploi du temps de Marchand : la zone 4 ne mène pas à la zone 2 (8h)".to_string()));
+    }
+
+    #[test]
+    fn test_hostilite_des_pnjs() {
+        let mut joueur = pnj_test(0, 1.0).personnage;
+        let mut garde = pnj_test(0, 1.0);
+        garde.garde = true;
+        assert!(!garde.est_hostile(&joueur));
+        joueur.progression.infamie = 1;
+        assert!(garde.est_hostile(&joueur));
+
+        let mut territorial = pnj_test(0, 1.0);
+        territorial.agressivite = Agressivite::Territorial;
+        assert!(!territorial.est_hostile(&joueur));
+        territorial.modifier_reputation(REPUTATION_MIN);
+        assert!(territorial.est_hostile(&joueur));
+        territorial.personnage.est_vivant = false;
+        assert!(!territorial.est_hostile(&joueur));
+
+        let json = r#"{ "id": 7, "nom": "Sanglier", "description": "desc", "force": 50,
+            "inventaire": { "taille": 1, "objets": [] }, "parties_du_corps": [],
+            "argent": 0, "est_vivant": true, "agressivite": "Territorial" }"#;
+        let mob: Mob = serde_json::from_str(json).unwrap();
+        assert_eq!(mob.agressivite, Agressivite::Territorial);
+        assert!(!mob.agressivite.poursuit());
+    }
+
+    #[test]
+    fn test_pas_de_nouvelle_attaque_apres_une_fuite() {
+        let joueur = pnj_test(0, 1.0).personnage;
+        let mut pnjs = vec![pnj_test(0, 1.0), pnj_test(0, 1.0)];
+        pnjs[1].agressivite = Agressivite::Agressif;
+        assert_eq!(PNJ::premier_hostile(&mut pnjs, &joueur, 1), Some(1));
+        pnjs[1].joueur_enfui = true;
+        assert_eq!(PNJ::premier_hostile(&mut pnjs, &joueur, 1), None);
+        // Le joueur quitte la zone puis revient : le PNJ l'attaque de nouveau
+        assert_eq!(PNJ::premier_hostile(&mut pnjs, &joueur, 2), None);
+        assert_eq!(PNJ::premier_hostile(&mut pnjs, &joueur, 1), Some(1));
+    }
+
+    #[test]
+    fn test_temoins_d_un_meurtre() {
+        let mut pnjs = vec![pnj_test(0, 1.0), pnj_test(0, 1.0), pnj_test(0, 1.0)];
+        pnjs[2].zone_id = 2;
+        PNJ::reagir_violence(&mut pnjs, 0, &ResultatInteraction::Attaque);
+        assert_eq!(pnjs[1].reputation, -20);
+        assert!(!pnjs[1].refuse_commerce());
+        PNJ::reagir_violence(&mut pnjs, 0, &ResultatInteraction::Meurtre);
+        assert!(pnjs[1].refuse_commerce());
+        // Les PNJs d'une autre zone et la victime elle-même ne sont pas des témoins
+        assert_eq!(pnjs[2].reputation, 0);
+        assert_eq!(pnjs[0].reputation, 0);
+    }
+
+    #[test]
+    fn test_effets_de_dialogue() {
+        let mut pnj = pnj_test(0, 1.5);
+        let mut joueur = pnj_test(20, 1.0).personnage;
+        let effets = [
+            Effet::PrendreArgent { montant: 15 },
+            Effet::ModifierPrix { multiplicateur: 1.0 },
+            Effet::ModifierReputation { variation: 10 },
+            Effet::PoserDrapeau { nom: "aide".to_string() },
+        ];
+        for effet in &effets {
+            assert!(pnj.appliquer_effet(effet, &mut joueur, &mut [], 0).is_none());
+        }
+        assert_eq!(joueur.argent, 5);
+        assert_eq!(pnj.personnage.argent, 15);
+        assert_eq!(pnj.multiplicateur_prix, 1.0);
+        assert_eq!(pnj.reputation, 10);
+        assert!(joueur.progression.a_drapeau("aide"));
+
+        // Le joueur n'a pas l'objet demandé : la réponse échoue sans rien retirer
+        catalogue_test();
+        joueur.inventaire.objets = vec![ObjetInventaire { nombre: 1, objet_id: RELIQUE, instance: None }];
+        let prendre = Effet::PrendreObjet { objet_id: RELIQUE, nombre: 2 };
+        assert!(prendre.verifier(&joueur).is_err());
+        assert!(matches!(pnj.appliquer_effet(&prendre, &mut joueur, &mut [], 0), Some(ResultatInteraction::Aucun)));
+        assert_eq!(joueur.inventaire.objets[0].nombre, 1);
+        let prendre = Effet::PrendreObjet { objet_id: RELIQUE, nombre: 1 };
+        assert!(pnj.appliquer_effet(&prendre, &mut joueur, &mut [], 0).is_none());
+        assert!(joueur.inventaire.objets.is_empty());
+    }
+
+    #[test]
+    fn test_lecture_des_arbres_de_dialogue() {
+        let fichier = std::env::temp_dir().join(format!("pnj_dialogue_{}.json", std::process::id()));
+        let chemin = fichier.to_str().unwrap();
+        let mut pnj = pnj_test(0, 1.0);
+        pnj.arbre_dialogue = Some(ArbreDialogue::par_defaut(&["Bonjour.".to_string()]));
+        std::fs::write(&fichier, serde_json::to_string(&vec![pnj.clone()]).unwrap()).unwrap();
+        let pnjs = PNJ::charger_pnj(chemin).unwrap();
+        assert!(pnjs[0].arbre_dialogue.is_some());
+
+        // Un nœud suivant inconnu est une erreur de données, pas un arbre ignoré
+        pnj.arbre_dialogue.as_mut().unwrap().depart = "absent".to_string();
+        std::fs::write(&fichier, serde_json::to_string(&vec![pnj]).unwrap()).unwrap();
+        let erreur = PNJ::charger_pnj(chemin).unwrap_err();
+        assert_eq!(erreur.kind(), io::ErrorKind::InvalidData);
+        std::fs::remove_file(&fichier).unwrap();
+    }
+
+    #[test]
+    fn test_repartition_des_points() {
+        let mut joueur = pnj_test(0, 1.0).personnage;
+        let courbe = CourbeProgression {
+            experience_requise: vec![0, 10],
+            experience_par_force: 1.0,
+            points_par_niveau: 3,
+            gain_force: 2,
+            gain_vie: 5,
+            gain_charge: 1000,
+            gain_attribut: 3,
+        };
+        assert!(joueur.repartir_point(Caracteristique::Force, &courbe).is_err());
+        joueur.progression.ajouter_experience(10, &courbe);
+        let poids_max = joueur.poids_max();
+
+        joueur.repartir_point(Caracteristique::Force, &courbe).unwrap();
+        joueur.repartir_point(Caracteristique::Vie, &courbe).unwrap();
+        joueur.repartir_point(Caracteristique::Charge, &courbe).unwrap();
+        assert_eq!(joueur.force, 102);
+        assert_eq!(joueur.parties_du_corps[0].vie_max(), 55);
+        assert_eq!(joueur.parties_du_corps[0].vie_actuelle(), 55);
+        assert_eq!(joueur.poids_max(), poids_max + 2 * POIDS_PAR_FORCE + 1000);
+        assert!(joueur.repartir_point(Caracteristique::Force, &courbe).is_err());
+    }
+
+    #[test]
+    fn test_attributs_par_defaut_et_fuite() {
+        // Un personnage sauvegardé avant l'ajout des attributs reçoit les valeurs par défaut
+        let json = r#"{ "id": 1, "nom": "Ancien", "description": "desc", "force": 100,
+            "inventaire": { "taille": 1, "objets": [] }, "parties_du_corps": [],
+            "argent": 0, "est_vivant": true }"#;
+        let mut ancien: Personnage = serde_json::from_str(json).unwrap();
+        assert_eq!(ancien.attributs, Attributs::default());
+        assert_eq!(ancien.facteur_guerison(), 1.0);
+
+        let mut adversaire = ancien.clone();
+        assert_eq!(ancien.chance_fuite(&adversaire), 0.5);
+        adversaire.attributs.agilite = 100;
+        ancien.attributs.agilite = 0;
+        assert_eq!(ancien.chance_fuite(&adversaire), 0.1);
+        assert_eq!(creer_parties_du_corps(100)[1].vie_max(), 150);
+    }
+
+    #[test]
+    fn test_creation_par_classe() {
+        catalogue_test();
+        let classe = Classe {
+            nom: "Lancier".to_string(),
+            description: "desc".to_string(),
+            force: 110,
+            attributs: Attributs { agilite: 60, endurance: 50, perception: 40, charisme: 30 },
+            argent: 25,
+            equipement: vec![ObjetInventaire { nombre: 2, objet_id: LANCE, instance: None }],
+            zone_depart: 2,
+            points_a_repartir: 3,
+        };
+        let joueur = Joueur::depuis_classe(7, "Lina", "desc", &classe);
+        assert_eq!(joueur.force, 110);
+        assert_eq!(joueur.attributs.agilite, 60);
+        assert_eq!(joueur.progression.zone_actuelle, 2);
+        assert_eq!(joueur.progression.points_a_repartir, 3);
+        // Une lance est portée au bras droit, l'autre va dans l'inventaire
+        assert_eq!(joueur.parties_du_corps[2].equipement().objets[0].objet_id, LANCE);
+        assert_eq!(joueur.inventaire.objets[0].nombre, 1);
+
+        assert!(Joueur::valider_nom("  ", &[]).is_err());
+        assert!(Joueur::valider_nom("lina", &[joueur]).is_err());
+        assert!(Joueur::valider_nom("Marc", &[]).is_ok());
+    }
 }
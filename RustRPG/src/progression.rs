@@ -0,0 +1,498 @@
+use std::collections::BTreeMap;
+use std::fs;
+use once_cell::sync::Lazy;
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+use crate::inventaire::Inventaire;
+use crate::reserve::Reserve;
+
+/// Identifiant du monde décrit par `zone.json`.
+pub const MONDE_CLASSIQUE: &str = "classique";
+
+/// Courbe de progression chargée depuis `src/json/niveaux.json` au premier accès.
+pub static COURBE_PROGRESSION: Lazy<CourbeProgression> = Lazy::new(|| {
+    CourbeProgression::charger("src/json/niveaux.json")
+        .expect("⚠️ Impossible de charger la courbe de progression !")
+});
+
+/// Paramètres de la montée en niveau : expérience requise et gains par point réparti.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CourbeProgression {
+    /// Expérience totale requise pour atteindre chaque niveau ; l'indice 0 correspond
+    /// au niveau 1. Le dernier élément fixe le niveau maximal.
+    pub experience_requise: Vec<u32>,
+    /// Expérience gagnée par point de force d'un adversaire vaincu.
+    pub experience_par_force: f32,
+    /// Points à répartir gagnés à chaque niveau.
+    pub points_par_niveau: u32,
+    /// Force gagnée par point réparti.
+    pub gain_force: u8,
+    /// Vie maximale gagnée par chaque partie du corps par point réparti.
+    pub gain_vie: u32,
+    /// Charge maximale (en grammes) gagnée par point réparti.
+    pub gain_charge: u32,
+    /// Agilité, endurance, perception ou charisme gagné par point réparti.
+    pub gain_attribut: u8,
+}
+
+impl CourbeProgression {
+    /// Charge la courbe depuis un fichier JSON.
+    ///
+    /// # Arguments
+    /// * `fichier` - Chemin du fichier JSON.
+    pub fn charger(fichier: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let contenu = fs::read_to_string(fichier)?;
+        Ok(serde_json::from_str(&contenu)?)
+    }
+
+    /// Renvoie le niveau correspondant à une quantité d'expérience.
+    ///
+    /// # Arguments
+    /// * `experience` - Expérience totale accumulée.
+    pub fn niveau_pour(&self, experience: u32) -> u32 {
+        (self.experience_requise.iter().filter(|&&requise| experience >= requise).count() as u32).max(1)
+    }
+
+    /// Renvoie l'expérience totale requise pour le niveau suivant `niveau`,
+    /// ou `None` si `niveau` est le niveau maximal.
+    pub fn experience_niveau_suivant(&self, niveau: u32) -> Option<u32> {
+        self.experience_requise.get(niveau as usize).copied()
+    }
+
+    /// Renvoie l'expérience gagnée en vainquant un adversaire de cette force.
+    pub fn experience_combat(&self, force_adversaire: u8) -> u32 {
+        (force_adversaire as f32 * self.experience_par_force).round() as u32
+    }
+}
+
+/// Regroupe l'avancement propre à un joueur (drapeaux d'histoire, quêtes, etc.),
+/// sauvegardé avec son personnage.
+///
+/// Les PNJs et les mobs en possèdent une vide.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Progression {
+    /// Drapeaux d'histoire posés par les dialogues (ex. : "a_rencontre_marcus").
+    #[serde(default)]
+    pub drapeaux: Vec<String>,
+    /// Quêtes acceptées et non encore rendues.
+    #[serde(default)]
+    pub quetes_en_cours: Vec<SuiviQuete>,
+    /// Identifiants des quêtes rendues.
+    #[serde(default)]
+    pub quetes_terminees: Vec<u32>,
+    /// Expérience totale accumulée.
+    #[serde(default)]
+    pub experience: u32,
+    /// Niveau actuel.
+    #[serde(default = "niveau_initial")]
+    pub niveau: u32,
+    /// Points gagnés en montant de niveau et pas encore répartis.
+    #[serde(default)]
+    pub points_a_repartir: u32,
+    /// Charge maximale supplémentaire (en grammes) obtenue en répartissant des points.
+    #[serde(default)]
+    pub bonus_charge: u32,
+    /// Identifiant de la zone où se trouve le joueur, restaurée au chargement.
+    #[serde(default = "zone_initiale")]
+    pub zone_actuelle: u8,
+    /// Identifiants des PNJs tués par le joueur, qui restent morts d'une partie à l'autre.
+    #[serde(default)]
+    pub pnjs_tues: Vec<u32>,
+    /// Nombre de crimes (agressions de PNJs) pour lesquels le joueur n'a pas payé
+    /// d'amende ; les gardes l'attaquent tant qu'il est positif.
+    #[serde(default)]
+    pub infamie: u32,
+    /// Identifiants des zones déjà visitées par le joueur.
+    #[serde(default)]
+    pub zones_visitees: Vec<u8>,
+    /// Connexions connues du joueur, sous la forme `(zone de départ, zone d'arrivée)` :
+    /// les sorties d'une zone sont découvertes en la visitant.
+    #[serde(default)]
+    pub connexions_connues: Vec<(u8, u8)>,
+    /// Identifiants des zones achetées par le joueur.
+    #[serde(default)]
+    pub zones_achetees: Vec<u8>,
+    /// Identifiants des zones ouvertes au joueur par une quête ou un dialogue,
+    /// qu'il n'a pas eu à acheter.
+    #[serde(default)]
+    pub zones_ouvertes: Vec<u8>,
+    /// Date jusqu'à laquelle les revenus des zones achetées ont été perçus.
+    #[serde(default)]
+    pub dernier_revenu: Option<DateTime<Utc>>,
+    /// Réserve personnelle, accessible depuis les zones achetées.
+    #[serde(default)]
+    pub reserve: Reserve,
+    /// État des PNJs propre à ce joueur, par identifiant de PNJ.
+    #[serde(default)]
+    pub etats_pnjs: BTreeMap<u32, EtatPnj>,
+    /// Zone où se trouve chaque PNJ dans le monde actuel, par identifiant de PNJ.
+    #[serde(default)]
+    pub positions_pnjs: BTreeMap<u32, u32>,
+    /// Monde auquel se rapportent la zone actuelle, les zones visitées, connues,
+    /// achetées et ouvertes, et les positions des PNJs.
+    #[serde(default = "monde_classique")]
+    pub monde: String,
+    /// Avancement dans les autres mondes, mis de côté par `changer_monde`.
+    #[serde(default)]
+    pub autres_mondes: BTreeMap<String, ProgressionMonde>,
+}
+
+fn niveau_initial() -> u32 {
+    1
+}
+
+fn zone_initiale() -> u8 {
+    1
+}
+
+fn monde_classique() -> String {
+    MONDE_CLASSIQUE.to_string()
+}
+
+impl Default for Progression {
+    fn default() -> Self {
+        Progression {
+            drapeaux: vec![],
+            quetes_en_cours: vec![],
+            quetes_terminees: vec![],
+            experience: 0,
+            niveau: niveau_initial(),
+            points_a_repartir: 0,
+            bonus_charge: 0,
+            zone_actuelle: zone_initiale(),
+            pnjs_tues: vec![],
+            infamie: 0,
+            zones_visitees: vec![],
+            connexions_connues: vec![],
+            zones_achetees: vec![],
+            zones_ouvertes: vec![],
+            dernier_revenu: None,
+            reserve: Reserve::default(),
+            etats_pnjs: BTreeMap::new(),
+            positions_pnjs: BTreeMap::new(),
+            monde: monde_classique(),
+            autres_mondes: BTreeMap::new(),
+        }
+    }
+}
+
+/// Partie de la progression propre à un monde : les identifiants de zones d'un
+/// monde généré ne désignent pas les mêmes lieux que ceux du monde classique.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProgressionMonde {
+    /// Identifiant de la zone où se trouvait le joueur.
+    #[serde(default = "zone_initiale")]
+    pub zone_actuelle: u8,
+    /// Identifiants des zones visitées.
+    #[serde(default)]
+    pub zones_visitees: Vec<u8>,
+    /// Connexions connues, sous la forme `(zone de départ, zone d'arrivée)`.
+    #[serde(default)]
+    pub connexions_connues: Vec<(u8, u8)>,
+    /// Identifiants des zones achetées.
+    #[serde(default)]
+    pub zones_achetees: Vec<u8>,
+    /// Identifiants des zones ouvertes par une quête ou un dialogue.
+    #[serde(default)]
+    pub zones_ouvertes: Vec<u8>,
+    /// Zone où se trouvait chaque PNJ, par identifiant de PNJ.
+    #[serde(default)]
+    pub positions_pnjs: BTreeMap<u32, u32>,
+}
+
+impl Default for ProgressionMonde {
+    fn default() -> Self {
+        ProgressionMonde {
+            zone_actuelle: zone_initiale(),
+            zones_visitees: vec![],
+            connexions_connues: vec![],
+            zones_achetees: vec![],
+            zones_ouvertes: vec![],
+            positions_pnjs: BTreeMap::new(),
+        }
+    }
+}
+
+/// État d'un PNJ tel que le joueur l'a laissé : les achats et les ventes d'une
+/// partie se retrouvent dans la suivante.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EtatPnj {
+    /// Inventaire du PNJ (stock restant d'un marchand).
+    pub inventaire: Inventaire,
+    /// Argent du PNJ.
+    pub argent: u32,
+    /// Date du dernier réapprovisionnement du stock.
+    pub dernier_reapprovisionnement: DateTime<Utc>,
+    /// Réputation du joueur auprès du PNJ.
+    #[serde(default)]
+    pub reputation: i32,
+}
+
+/// Avancement d'une quête en cours.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SuiviQuete {
+    /// Identifiant de la quête dans `quete.json`.
+    pub quete_id: u32,
+    /// Progression de chaque objectif, dans l'ordre des objectifs de la quête.
+    pub progres: Vec<u32>,
+}
+
+impl Progression {
+    /// Vérifie si un drapeau d'histoire est posé.
+    ///
+    /// # Arguments
+    /// * `nom` - Nom du drapeau.
+    pub fn a_drapeau(&self, nom: &str) -> bool {
+        self.drapeaux.iter().any(|d| d == nom)
+    }
+
+    /// Pose un drapeau d'histoire s'il ne l'est pas déjà.
+    ///
+    /// # Arguments
+    /// * `nom` - Nom du drapeau.
+    pub fn poser_drapeau(&mut self, nom: &str) {
+        if !self.a_drapeau(nom) {
+            self.drapeaux.push(nom.to_string());
+        }
+    }
+
+    /// Renvoie le suivi d'une quête si elle est en cours.
+    ///
+    /// # Arguments
+    /// * `quete_id` - Identifiant de la quête.
+    pub fn suivi_quete(&self, quete_id: u32) -> Option<&SuiviQuete> {
+        self.quetes_en_cours.iter().find(|s| s.quete_id == quete_id)
+    }
+
+    /// Vérifie si une quête a déjà été rendue.
+    ///
+    /// # Arguments
+    /// * `quete_id` - Identifiant de la quête.
+    pub fn quete_terminee(&self, quete_id: u32) -> bool {
+        self.quetes_terminees.contains(&quete_id)
+    }
+
+    /// Vérifie si le joueur a déjà visité une zone.
+    ///
+    /// # Arguments
+    /// * `zone_id` - Identifiant de la zone.
+    pub fn a_visite(&self, zone_id: u8) -> bool {
+        self.zones_visitees.contains(&zone_id)
+    }
+
+    /// Marque une zone comme visitée si elle ne l'est pas déjà.
+    ///
+    /// # Arguments
+    /// * `zone_id` - Identifiant de la zone.
+    pub fn visiter_zone(&mut self, zone_id: u8) {
+        if !self.a_visite(zone_id) {
+            self.zones_visitees.push(zone_id);
+        }
+    }
+
+    /// Marque une zone comme visitée et mémorise ses sorties.
+    ///
+    /// # Arguments
+    /// * `zone_id` - Identifiant de la zone visitée.
+    /// * `voisines` - Identifiants des zones auxquelles mènent ses sorties.
+    pub fn decouvrir_zone(&mut self, zone_id: u8, voisines: &[u8]) {
+        self.visiter_zone(zone_id);
+        for &voisine in voisines {
+            if !self.connait_connexion(zone_id, voisine) {
+                self.connexions_connues.push((zone_id, voisine));
+            }
+        }
+    }
+
+    /// Vérifie si le joueur connaît la connexion d'une zone vers une autre.
+    ///
+    /// # Arguments
+    /// * `depart` - Identifiant de la zone de départ.
+    /// * `arrivee` - Identifiant de la zone d'arrivée.
+    pub fn connait_connexion(&self, depart: u8, arrivee: u8) -> bool {
+        self.connexions_connues.contains(&(depart, arrivee))
+    }
+
+    /// Vérifie si le joueur a connaissance d'une zone : il l'a visitée ou
+    /// connaît une sortie qui y mène.
+    ///
+    /// # Arguments
+    /// * `zone_id` - Identifiant de la zone.
+    pub fn connait_zone(&self, zone_id: u8) -> bool {
+        self.a_visite(zone_id) || self.connexions_connues.iter().any(|&(_, arrivee)| arrivee == zone_id)
+    }
+
+    /// Vérifie si le joueur possède une zone.
+    ///
+    /// # Arguments
+    /// * `zone_id` - Identifiant de la zone.
+    pub fn possede_zone(&self, zone_id: u8) -> bool {
+        self.zones_achetees.contains(&zone_id)
+    }
+
+    /// Enregistre l'achat d'une zone.
+    ///
+    /// # Arguments
+    /// * `zone_id` - Identifiant de la zone achetée.
+    pub fn acheter_zone(&mut self, zone_id: u8) {
+        if !self.possede_zone(zone_id) {
+            self.zones_achetees.push(zone_id);
+        }
+    }
+
+    /// Enregistre l'ouverture d'une zone par une quête ou un dialogue.
+    ///
+    /// # Arguments
+    /// * `zone_id` - Identifiant de la zone ouverte.
+    pub fn ouvrir_zone(&mut self, zone_id: u8) {
+        if !self.zones_ouvertes.contains(&zone_id) {
+            self.zones_ouvertes.push(zone_id);
+        }
+    }
+
+    /// Vérifie si le joueur a accès à une zone, parce qu'il l'a achetée ou
+    /// qu'une quête ou un dialogue la lui a ouverte.
+    ///
+    /// # Arguments
+    /// * `zone_id` - Identifiant de la zone.
+    pub fn a_acces_zone(&self, zone_id: u8) -> bool {
+        self.possede_zone(zone_id) || self.zones_ouvertes.contains(&zone_id)
+    }
+
+    /// Passe dans un autre monde : l'avancement dans le monde actuel est mis de
+    /// côté et celui du nouveau monde est restauré, ou repart de zéro si le joueur
+    /// n'y est jamais allé.
+    ///
+    /// # Arguments
+    /// * `monde` - Identifiant du monde (`MONDE_CLASSIQUE` ou celui d'un monde généré).
+    pub fn changer_monde(&mut self, monde: &str) {
+        if self.monde == monde {
+            return;
+        }
+        let nouveau = self.autres_mondes.remove(monde).unwrap_or_default();
+        let ancien = ProgressionMonde {
+            zone_actuelle: std::mem::replace(&mut self.zone_actuelle, nouveau.zone_actuelle),
+            zones_visitees: std::mem::replace(&mut self.zones_visitees, nouveau.zones_visitees),
+            connexions_connues: std::mem::replace(&mut self.connexions_connues, nouveau.connexions_connues),
+            zones_achetees: std::mem::replace(&mut self.zones_achetees, nouveau.zones_achetees),
+            zones_ouvertes: std::mem::replace(&mut self.zones_ouvertes, nouveau.zones_ouvertes),
+            positions_pnjs: std::mem::replace(&mut self.positions_pnjs, nouveau.positions_pnjs),
+        };
+        let ancien_monde = std::mem::replace(&mut self.monde, monde.to_string());
+        self.autres_mondes.insert(ancien_monde, ancien);
+    }
+
+    /// Ajoute de l'expérience et fait monter de niveau si nécessaire, chaque niveau
+    /// gagné rapportant des points à répartir.
+    ///
+    /// # Arguments
+    /// * `experience` - Expérience gagnée.
+    /// * `courbe` - La courbe de progression.
+    ///
+    /// # Retour
+    /// Le nombre de niveaux gagnés.
+    pub fn ajouter_experience(&mut self, experience: u32, courbe: &CourbeProgression) -> u32 {
+        self.experience = self.experience.saturating_add(experience);
+        let nouveau_niveau = courbe.niveau_pour(self.experience).max(self.niveau);
+        let niveaux_gagnes = nouveau_niveau - self.niveau;
+        self.niveau = nouveau_niveau;
+        self.points_a_repartir += niveaux_gagnes * courbe.points_par_niveau;
+        niveaux_gagnes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_courbe_de_progression() {
+        let courbe: CourbeProgression = serde_json::from_str(r#"{
+            "experience_requise": [0, 100, 300],
+            "experience_par_force": 0.5,
+            "points_par_niveau": 2,
+            "gain_force": 2,
+            "gain_vie": 5,
+            "gain_charge": 1000,
+            "gain_attribut": 3
+        }"#).unwrap();
+        let mut progression = Progression::default();
+        assert_eq!(progression.niveau, 1);
+        assert_eq!(courbe.experience_combat(101), 51);
+
+        assert_eq!(progression.ajouter_experience(99, &courbe), 0);
+        assert_eq!(progression.ajouter_experience(250, &courbe), 2);
+        assert_eq!(progression.niveau, 3);
+        assert_eq!(progression.points_a_repartir, 4);
+        // Le niveau maximal est atteint
+        assert_eq!(progression.ajouter_experience(10_000, &courbe), 0);
+        assert_eq!(courbe.experience_niveau_suivant(progression.niveau), None);
+    }
+
+    #[test]
+    fn test_decouverte_des_zones() {
+        let mut progression = Progression::default();
+        assert!(!progression.connait_zone(1));
+        progression.decouvrir_zone(1, &[2, 3]);
+        progression.decouvrir_zone(1, &[2, 3]);
+        assert!(progression.a_visite(1));
+        assert_eq!(progression.connexions_connues, vec![(1, 2), (1, 3)]);
+        assert!(progression.connait_zone(2) && !progression.a_visite(2));
+        assert!(progression.connait_connexion(1, 2) && !progression.connait_connexion(2, 1));
+        assert!(!progression.connait_zone(4));
+    }
+
+    #[test]
+    fn test_zones_ouvertes_distinctes_des_achats() {
+        let mut progression = Progression::default();
+        progression.ouvrir_zone(3);
+        progression.ouvrir_zone(3);
+        progression.acheter_zone(4);
+        assert_eq!(progression.zones_ouvertes, vec![3]);
+        assert!(progression.a_acces_zone(3) && !progression.possede_zone(3));
+        assert!(progression.a_acces_zone(4));
+        assert!(!progression.a_acces_zone(5));
+
+        // Une sauvegarde antérieure n'a aucune zone ouverte
+        let ancienne: Progression = serde_json::from_str("{}").unwrap();
+        assert!(ancienne.zones_ouvertes.is_empty());
+        let relue: Progression = serde_json::from_str(&serde_json::to_string(&progression).unwrap()).unwrap();
+        assert_eq!(relue.zones_ouvertes, vec![3]);
+    }
+
+    #[test]
+    fn test_progression_propre_a_chaque_monde() {
+        let mut progression = Progression { zone_actuelle: 4, ..Default::default() };
+        progression.decouvrir_zone(4, &[5]);
+        progression.acheter_zone(4);
+        progression.ouvrir_zone(5);
+        progression.positions_pnjs.insert(1, 5);
+        progression.poser_drapeau("aide");
+
+        // Les zones 4 et 5 d'un monde généré sont d'autres lieux
+        progression.changer_monde("genere_7_10");
+        assert_eq!(progression.zone_actuelle, 1);
+        assert!(!progression.a_visite(4) && !progression.connait_zone(5));
+        assert!(!progression.a_acces_zone(4) && !progression.a_acces_zone(5));
+        assert!(progression.positions_pnjs.is_empty());
+        assert!(progression.a_drapeau("aide"));
+        progression.visiter_zone(2);
+
+        let mut progression: Progression = serde_json::from_str(&serde_json::to_string(&progression).unwrap()).unwrap();
+        progression.changer_monde(MONDE_CLASSIQUE);
+        assert_eq!(progression.zone_actuelle, 4);
+        assert!(progression.possede_zone(4) && progression.a_acces_zone(5));
+        assert_eq!(progression.positions_pnjs.get(&1), Some(&5));
+        assert!(!progression.a_visite(2));
+        progression.changer_monde("genere_7_10");
+        assert!(progression.a_visite(2));
+    }
+
+    #[test]
+    fn test_courbe_du_jeu_croissante() {
+        let courbe = &*COURBE_PROGRESSION;
+        assert_eq!(courbe.experience_requise[0], 0);
+        assert!(courbe.experience_requise.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(courbe.niveau_pour(0), 1);
+    }
+}
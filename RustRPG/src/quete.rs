@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+use once_cell::sync::Lazy;
+use serde::{Serialize, Deserialize};
+use crate::objet::OBJETS_DISPONIBLES;
+use crate::personnage::{Mob, Personnage};
+use crate::progression::SuiviQuete;
+use crate::zone::Zone;
+
+/// Quête chargée depuis `quete.json` et confiée au joueur par un PNJ
+/// (effet de dialogue `DonnerQuete`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Quete {
+    /// Identifiant unique de la quête.
+    pub id: u32,
+    /// Nom de la quête, affiché dans le journal.
+    pub nom: String,
+    /// Description de la quête.
+    pub description: String,
+    /// Objectifs à remplir pour pouvoir rendre la quête.
+    pub objectifs: Vec<Objectif>,
+    /// Récompenses reçues en rendant la quête.
+    #[serde(default)]
+    pub recompenses: Vec<Recompense>,
+}
+
+/// Objectif d'une quête.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum Objectif {
+    /// Vaincre `nombre` mobs ayant l'identifiant `mob_id`.
+    TuerMobs { mob_id: u32, nombre: u32 },
+    /// Rapporter `nombre` exemplaires d'un objet ; ils sont retirés en rendant la quête.
+    ApporterObjet { objet_id: u8, nombre: u8 },
+    /// Se rendre dans une zone.
+    AtteindreZone { zone_id: u8 },
+    /// Ouvrir un coffre précis d'une zone.
+    OuvrirCoffre { zone_id: u8, coffre_id: u8 },
+}
+
+/// Récompense d'une quête.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum Recompense {
+    /// Pièces données au joueur.
+    Argent { montant: u32 },
+    /// Objets ajoutés à l'inventaire du joueur.
+    Objet { objet_id: u8, nombre: u8 },
+    /// Zone ouverte sans avoir à l'acheter.
+    OuvrirZone { zone_id: u8 },
+    /// Expérience gagnée.
+    Experience { montant: u32 },
+}
+
+/// Événement de jeu pouvant faire avancer les objectifs des quêtes en cours.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Evenement {
+    /// Le joueur a vaincu le mob d'identifiant donné.
+    MobTue(u32),
+    /// Le joueur est arrivé dans la zone d'identifiant donné.
+    ZoneAtteinte(u8),
+    /// Le joueur a ouvert un coffre.
+    CoffreOuvert { zone_id: u8, coffre_id: u8 },
+}
+
+/// Collection globale des quêtes disponibles, indexées par identifiant.
+pub static QUETES_DISPONIBLES: Lazy<RwLock<HashMap<u32, Quete>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Ajoute une quête dans la collection globale des quêtes.
+///
+/// # Arguments
+/// * `quete` - La quête à ajouter.
+pub fn ajouter_quete(quete: Quete) {
+    QUETES_DISPONIBLES.write().unwrap().insert(quete.id, quete);
+}
+
+/// Renvoie le nom d'une quête, ou son identifiant si elle est inconnue.
+pub fn nom_quete(quete_id: u32) -> String {
+    QUETES_DISPONIBLES.read().unwrap()
+        .get(&quete_id)
+        .map(|q| q.nom.clone())
+        .unwrap_or_else(|| format!("Quête {}", quete_id))
+}
+
+impl Objectif {
+    /// Nombre de progrès nécessaires pour remplir l'objectif.
+    fn cible(&self) -> u32 {
+        match self {
+            Objectif::TuerMobs { nombre, .. } => *nombre,
+            Objectif::ApporterObjet { nombre, .. } => *nombre as u32,
+            Objectif::AtteindreZone { .. } | Objectif::OuvrirCoffre { .. } => 1,
+        }
+    }
+
+    /// Vérifie si l'événement fait avancer cet objectif.
+    fn concerne(&self, evenement: &Evenement) -> bool {
+        match (self, evenement) {
+            (Objectif::TuerMobs { mob_id, .. }, Evenement::MobTue(id)) => mob_id == id,
+            (Objectif::AtteindreZone { zone_id }, Evenement::ZoneAtteinte(id)) => zone_id == id,
+            (Objectif::OuvrirCoffre { zone_id, coffre_id }, Evenement::CoffreOuvert { zone_id: z, coffre_id: c }) => {
+                zone_id == z && coffre_id == c
+            }
+            _ => false,
+        }
+    }
+
+    /// Renvoie la progression actuelle de l'objectif pour ce joueur.
+    ///
+    /// Pour `ApporterObjet`, la progression est le nombre d'exemplaires dans l'inventaire.
+    fn progres(&self, joueur: &Personnage, suivi: &SuiviQuete, index: usize) -> u32 {
+        match self {
+            Objectif::ApporterObjet { objet_id, .. } => joueur.inventaire.objets.iter()
+                .filter(|o| o.objet_id == *objet_id)
+                .map(|o| o.nombre as u32)
+                .sum(),
+            _ => suivi.progres.get(index).copied().unwrap_or(0),
+        }
+    }
+
+    /// Décrit l'objectif pour le journal.
+    ///
+    /// # Arguments
+    /// * `zones` - Les zones du jeu, pour afficher leur nom.
+    /// * `mobs` - Les mobs du jeu, pour afficher leur nom.
+    fn decrire(&self, zones: &[Zone], mobs: &[Mob]) -> String {
+        let nom_zone = |id: &u8| zones.iter().find(|z| z.id == *id)
+            .map(|z| z.nom.clone())
+            .unwrap_or_else(|| format!("zone {}", id));
+        match self {
+            Objectif::TuerMobs { mob_id, nombre } => {
+                let nom = mobs.iter().find(|m| m.personnage.id == *mob_id)
+                    .map(|m| m.personnage.nom.clone())
+                    .unwrap_or_else(|| format!("mob {}", mob_id));
+                format!("Vaincre {} x{}", nom, nombre)
+            }
+            Objectif::ApporterObjet { objet_id, nombre } => {
+                let nom = OBJETS_DISPONIBLES.read().unwrap().get(objet_id)
+                    .map(|o| o.nom.clone())
+                    .unwrap_or_else(|| format!("objet {}", objet_id));
+                format!("Rapporter {} x{}", nom, nombre)
+            }
+            Objectif::AtteindreZone { zone_id } => format!("Se rendre à {}", nom_zone(zone_id)),
+            Objectif::OuvrirCoffre { zone_id, coffre_id } => {
+                format!("Ouvrir le coffre n°{} de {}", coffre_id, nom_zone(zone_id))
+            }
+        }
+    }
+}
+
+impl Quete {
+    /// Vérifie si tous les objectifs de la quête sont remplis par le joueur.
+    ///
+    /// # Retour
+    /// `false` si la quête n'est pas en cours pour ce joueur.
+    pub fn est_accomplie(&self, joueur: &Personnage) -> bool {
+        match joueur.progression.suivi_quete(self.id) {
+            Some(suivi) => self.objectifs.iter().enumerate()
+                .all(|(i, objectif)| objectif.progres(joueur, suivi, i) >= objectif.cible()),
+            None => false,
+        }
+    }
+}
+
+/// Confie une quête au joueur.
+///
+/// # Arguments
+/// * `joueur` - Le personnage du joueur.
+/// * `quete_id` - Identifiant de la quête dans `QUETES_DISPONIBLES`.
+///
+/// # Retour
+/// `Err` si la quête est inconnue, déjà en cours ou déjà terminée.
+pub fn accepter_quete(joueur: &mut Personnage, quete_id: u32) -> Result<(), String> {
+    let quetes = QUETES_DISPONIBLES.read().unwrap();
+    let quete = quetes.get(&quete_id).ok_or(format!("Quête {} inconnue", quete_id))?;
+    if joueur.progression.suivi_quete(quete_id).is_some() {
+        return Err(format!("La quête « {} » est déjà en cours", quete.nom));
+    }
+    if joueur.progression.quete_terminee(quete_id) {
+        return Err(format!("La quête « {} » est déjà terminée", quete.nom));
+    }
+    joueur.progression.quetes_en_cours.push(SuiviQuete {
+        quete_id,
+        progres: vec![0; quete.objectifs.len()],
+    });
+    println!("📜 Nouvelle quête : {} — {}", quete.nom, quete.description);
+    Ok(())
+}
+
+/// Fait avancer les objectifs des quêtes en cours concernés par un événement.
+///
+/// # Arguments
+/// * `joueur` - Le personnage du joueur.
+/// * `evenement` - L'événement survenu.
+pub fn signaler(joueur: &mut Personnage, evenement: &Evenement) {
+    let quetes = QUETES_DISPONIBLES.read().unwrap();
+    for suivi in joueur.progression.quetes_en_cours.iter_mut() {
+        let Some(quete) = quetes.get(&suivi.quete_id) else { continue };
+        for (i, objectif) in quete.objectifs.iter().enumerate() {
+            if i < suivi.progres.len() && objectif.concerne(evenement) && suivi.progres[i] < objectif.cible() {
+                suivi.progres[i] += 1;
+                println!("📜 {} : objectif {}/{}", quete.nom, suivi.progres[i], objectif.cible());
+            }
+        }
+    }
+}
+
+/// Rend une quête accomplie : retire les objets demandés, donne les récompenses
+/// et marque la quête comme terminée.
+///
+/// Rien n'est modifié si les récompenses ne tiennent pas dans l'inventaire.
+///
+/// # Arguments
+/// * `joueur` - Le personnage du joueur.
+/// * `zones` - Les zones du jeu, pour les récompenses `OuvrirZone`.
+/// * `quete_id` - Identifiant de la quête.
+pub fn rendre_quete(joueur: &mut Personnage, zones: &mut [Zone], quete_id: u32) -> Result<(), String> {
+    let quete = QUETES_DISPONIBLES.read().unwrap()
+        .get(&quete_id)
+        .cloned()
+        .ok_or(format!("Quête {} inconnue", quete_id))?;
+    if !quete.est_accomplie(joueur) {
+        return Err(format!("La quête « {} » n'est pas encore accomplie", quete.nom));
+    }
+
+    // Les objets sont échangés sur une copie de l'inventaire pour rester atomique
+    let mut inventaire = joueur.inventaire.clone();
+    for objectif in &quete.objectifs {
+        if let Objectif::ApporterObjet { objet_id, nombre } = objectif {
+            for _ in 0..*nombre {
+                inventaire.retirer_par_id(*objet_id);
+            }
+        }
+    }
+    for recompense in &quete.recompenses {
+        if let Recompense::Objet { objet_id, nombre } = recompense {
+            inventaire.ajouter_objets(*objet_id, *nombre)
+                .map_err(|e| format!("{}, impossible de recevoir la récompense", e))?;
+        }
+    }
+    joueur.inventaire = inventaire;
+
+    for recompense in &quete.recompenses {
+        match recompense {
+            Recompense::Argent { montant } => {
+                joueur.ajouter_argent(*montant);
+                println!("Vous recevez {} pièces.", montant);
+            }
+            Recompense::Objet { objet_id, nombre } => {
+                let nom = OBJETS_DISPONIBLES.read().unwrap().get(objet_id)
+                    .map(|o| o.nom.clone())
+                    .unwrap_or_else(|| format!("ID {}", objet_id));
+                println!("Vous recevez {} x{}.", nom, nombre);
+            }
+            Recompense::OuvrirZone { zone_id } => {
+                if let Some(zone) = zones.iter_mut().find(|z| z.id == *zone_id) {
+                    zone.ouvert = true;
+                    joueur.progression.ouvrir_zone(zone.id);
+                    println!("L'accès à la zone {} vous est désormais ouvert !", zone.nom);
+                }
+            }
+            Recompense::Experience { montant } => joueur.gagner_experience(*montant),
+        }
+    }
+    joueur.progression.quetes_en_cours.retain(|s| s.quete_id != quete_id);
+    joueur.progression.quetes_terminees.push(quete_id);
+    println!("✅ Quête terminée : {}", quete.nom);
+    Ok(())
+}
+
+/// Affiche le journal des quêtes du joueur : quêtes en cours avec l'avancement
+/// de chaque objectif, puis quêtes terminées.
+///
+/// # Arguments
+/// * `joueur` - Le personnage du joueur.
+/// * `zones` - Les zones du jeu, pour afficher leur nom.
+/// * `mobs` - Les mobs du jeu, pour afficher leur nom.
+pub fn afficher_journal(joueur: &Personnage, zones: &[Zone], mobs: &[Mob]) {
+    let quetes = QUETES_DISPONIBLES.read().unwrap();
+    println!("\n=== Journal de quêtes ===");
+    println!("Quêtes en cours :");
+    if joueur.progression.quetes_en_cours.is_empty() {
+        println!("  Aucune");
+    }
+    for suivi in &joueur.progression.quetes_en_cours {
+        let Some(quete) = quetes.get(&suivi.quete_id) else { continue };
+        let statut = if quete.est_accomplie(joueur) { " (à rendre)" } else { "" };
+        println!("  📜 {}{} — {}", quete.nom, statut, quete.description);
+        for (i, objectif) in quete.objectifs.iter().enumerate() {
+            let progres = objectif.progres(joueur, suivi, i).min(objectif.cible());
+            println!("     - {} ({}/{})", objectif.decrire(zones, mobs), progres, objectif.cible());
+        }
+    }
+    println!("Quêtes terminées :");
+    if joueur.progression.quetes_terminees.is_empty() {
+        println!("  Aucune");
+    }
+    for quete_id in &joueur.progression.quetes_terminees {
+        println!("  ✅ {}", nom_quete(*quete_id));
+    }
+    println!("=========================\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outils_test::{catalogue_test, joueur_test, zone_test, PIERRE};
+
+    fn joueur_fort() -> Personnage {
+        let mut joueur = joueur_test(vec![], 5);
+        joueur.force = 100;
+        joueur
+    }
+
+    #[test]
+    fn test_avancement_et_rendu_de_quete() {
+        catalogue_test();
+        ajouter_quete(Quete {
+            id: 901,
+            nom: "Chasse".to_string(),
+            description: "desc".to_string(),
+            objectifs: vec![
+                Objectif::TuerMobs { mob_id: 3, nombre: 2 },
+                Objectif::ApporterObjet { objet_id: PIERRE, nombre: 2 },
+            ],
+            recompenses: vec![Recompense::Argent { montant: 50 }, Recompense::OuvrirZone { zone_id: 7 }],
+        });
+        let mut joueur = joueur_fort();
+        signaler(&mut joueur, &Evenement::MobTue(3));
+        assert!(joueur.progression.suivi_quete(901).is_none());
+
+        accepter_quete(&mut joueur, 901).unwrap();
+        assert!(accepter_quete(&mut joueur, 901).is_err());
+        signaler(&mut joueur, &Evenement::MobTue(3));
+        signaler(&mut joueur, &Evenement::MobTue(4));
+        signaler(&mut joueur, &Evenement::MobTue(3));
+        signaler(&mut joueur, &Evenement::MobTue(3));
+        assert_eq!(joueur.progression.suivi_quete(901).unwrap().progres[0], 2);
+        assert!(rendre_quete(&mut joueur, &mut [], 901).is_err());
+
+        joueur.inventaire.ajouter_objets(PIERRE, 3).unwrap();
+        let mut zones = vec![Zone { ouvert: false, ..zone_test(7) }];
+        rendre_quete(&mut joueur, &mut zones, 901).unwrap();
+        assert_eq!(joueur.argent, 50);
+        // La zone ouverte par la quête le reste d'une partie à l'autre
+        assert!(zones[0].ouvert);
+        assert!(joueur.progression.a_acces_zone(7) && !joueur.progression.possede_zone(7));
+        assert_eq!(joueur.inventaire.objets[0].nombre, 1);
+        assert!(joueur.progression.quete_terminee(901));
+        assert!(accepter_quete(&mut joueur, 901).is_err());
+    }
+
+    #[test]
+    fn test_recompense_inventaire_plein() {
+        catalogue_test();
+        ajouter_quete(Quete {
+            id: 902,
+            nom: "Exploration".to_string(),
+            description: "desc".to_string(),
+            objectifs: vec![Objectif::OuvrirCoffre { zone_id: 2, coffre_id: 5 }],
+            recompenses: vec![Recompense::Objet { objet_id: PIERRE, nombre: 1 }],
+        });
+        let mut joueur = joueur_fort();
+        joueur.inventaire.taille = 0;
+        accepter_quete(&mut joueur, 902).unwrap();
+        signaler(&mut joueur, &Evenement::CoffreOuvert { zone_id: 2, coffre_id: 5 });
+        assert!(QUETES_DISPONIBLES.read().unwrap()[&902].est_accomplie(&joueur));
+        assert!(rendre_quete(&mut joueur, &mut [], 902).is_err());
+        assert!(joueur.progression.suivi_quete(902).is_some());
+    }
+}
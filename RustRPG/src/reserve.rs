@@ -0,0 +1,297 @@
+use std::io;
+use serde::{Serialize, Deserialize};
+use crate::affichage;
+use crate::inventaire::{Inventaire, ObjetInventaire};
+use crate::objet::OBJETS_DISPONIBLES;
+use crate::personnage::{Personnage, PNJ};
+use crate::zone::Zone;
+
+/// Nombre d'emplacements d'une réserve neuve.
+const TAILLE_RESERVE_INITIALE: u8 = 10;
+/// Emplacements gagnés à chaque agrandissement.
+const EMPLACEMENTS_PAR_NIVEAU: u8 = 5;
+/// Nombre maximal d'agrandissements.
+const NIVEAU_MAX: u8 = 6;
+/// Prix du premier agrandissement, doublé à chaque niveau.
+const PRIX_AMELIORATION_BASE: u32 = 100;
+
+/// Réserve personnelle du joueur, accessible depuis ses zones achetées et
+/// sauvegardée avec sa progression.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Reserve {
+    /// Objets entreposés.
+    pub inventaire: Inventaire,
+    /// Argent déposé.
+    pub argent: u32,
+    /// Nombre d'agrandissements achetés.
+    pub niveau: u8,
+}
+
+impl Default for Reserve {
+    fn default() -> Self {
+        Reserve {
+            inventaire: Inventaire { taille: TAILLE_RESERVE_INITIALE, objets: vec![] },
+            argent: 0,
+            niveau: 0,
+        }
+    }
+}
+
+/// Renvoie le nom affiché d'un exemplaire, ou son identifiant s'il est inconnu du catalogue.
+fn nom_exemplaire(objet: &ObjetInventaire) -> String {
+    OBJETS_DISPONIBLES.read().unwrap()
+        .get(&objet.objet_id)
+        .map(|o| objet.nom_affiche(o))
+        .unwrap_or_else(|| format!("ID {}", objet.objet_id))
+}
+
+impl Reserve {
+    /// Prix du prochain agrandissement, ou `None` si la réserve est à sa taille maximale.
+    pub fn prix_amelioration(&self) -> Option<u32> {
+        (self.niveau < NIVEAU_MAX).then(|| PRIX_AMELIORATION_BASE * 2u32.pow(self.niveau as u32))
+    }
+
+    /// Dépose des exemplaires d'un emplacement de l'inventaire du joueur dans la
+    /// réserve, avec leurs propriétés.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur.
+    /// * `index` - Emplacement de l'objet dans l'inventaire du joueur.
+    /// * `quantite` - Nombre d'exemplaires à déposer.
+    ///
+    /// # Retour
+    /// `Err` avec l'explication à afficher si le dépôt est impossible.
+    pub fn deposer_objet(&mut self, joueur: &mut Personnage, index: usize, quantite: u8) -> Result<(), String> {
+        let Some(objet) = joueur.inventaire.objets.get(index) else {
+            return Err("Cet objet n'est pas dans votre inventaire.".to_string());
+        };
+        if quantite == 0 || objet.nombre < quantite {
+            return Err("Vous n'avez pas autant d'exemplaires de cet objet.".to_string());
+        }
+        if self.inventaire.ajouter_exemplaire(ObjetInventaire { nombre: quantite, ..objet.clone() }).is_err() {
+            return Err("La réserve est pleine, agrandissez-la pour y ranger davantage.".to_string());
+        }
+        joueur.inventaire.retirer_exemplaires(index, quantite);
+        Ok(())
+    }
+
+    /// Retire des exemplaires d'un emplacement de la réserve vers l'inventaire du
+    /// joueur, avec leurs propriétés.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur.
+    /// * `index` - Emplacement de l'objet dans la réserve.
+    /// * `quantite` - Nombre d'exemplaires à retirer.
+    ///
+    /// # Retour
+    /// `Err` avec l'explication à afficher si le retrait est impossible.
+    pub fn retirer_objet(&mut self, joueur: &mut Personnage, index: usize, quantite: u8) -> Result<(), String> {
+        let Some(objet) = self.inventaire.objets.get(index) else {
+            return Err("Cet objet n'est pas dans la réserve.".to_string());
+        };
+        if quantite == 0 || objet.nombre < quantite {
+            return Err("La réserve ne contient pas autant d'exemplaires de cet objet.".to_string());
+        }
+        let poids = OBJETS_DISPONIBLES.read().unwrap().get(&objet.objet_id).map(|o| o.poids).unwrap_or(0);
+        if !joueur.peut_porter(poids * quantite as u32) {
+            return Err("C'est trop lourd, vous ne pouvez pas porter autant !".to_string());
+        }
+        if joueur.inventaire.ajouter_exemplaire(ObjetInventaire { nombre: quantite, ..objet.clone() }).is_err() {
+            return Err("Pas assez de place dans votre inventaire !".to_string());
+        }
+        self.inventaire.retirer_exemplaires(index, quantite);
+        Ok(())
+    }
+
+    /// Dépose de l'argent du joueur dans la réserve.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur.
+    /// * `montant` - Somme à déposer.
+    pub fn deposer_argent(&mut self, joueur: &mut Personnage, montant: u32) -> Result<(), String> {
+        if montant == 0 || joueur.argent < montant {
+            return Err("Vous n'avez pas autant d'argent sur vous.".to_string());
+        }
+        joueur.retirer_argent(montant);
+        self.argent += montant;
+        Ok(())
+    }
+
+    /// Retire de l'argent de la réserve vers la bourse du joueur.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur.
+    /// * `montant` - Somme à retirer.
+    pub fn retirer_argent(&mut self, joueur: &mut Personnage, montant: u32) -> Result<(), String> {
+        if montant == 0 || self.argent < montant {
+            return Err("La réserve ne contient pas autant d'argent.".to_string());
+        }
+        self.argent -= montant;
+        joueur.ajouter_argent(montant);
+        Ok(())
+    }
+
+    /// Agrandit la réserve, payé avec l'argent que le joueur a sur lui.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur.
+    ///
+    /// # Retour
+    /// La nouvelle taille de la réserve.
+    pub fn ameliorer(&mut self, joueur: &mut Personnage) -> Result<u8, String> {
+        let Some(prix) = self.prix_amelioration() else {
+            return Err("Votre réserve ne peut plus être agrandie.".to_string());
+        };
+        if joueur.argent < prix {
+            return Err(format!("Il vous faut {} pièces pour agrandir la réserve.", prix));
+        }
+        joueur.retirer_argent(prix);
+        self.niveau += 1;
+        self.inventaire.taille += EMPLACEMENTS_PAR_NIVEAU;
+        Ok(self.inventaire.taille)
+    }
+}
+
+/// Demande un nombre au joueur.
+fn lire_nombre(message: &str) -> Option<u32> {
+    println!("{}", message);
+    let mut saisie = String::new();
+    io::stdin().read_line(&mut saisie).expect("❌ Erreur de lecture !");
+    saisie.trim().parse().ok()
+}
+
+/// Fait choisir au joueur un objet d'un inventaire puis une quantité.
+///
+/// # Retour
+/// L'emplacement de l'objet et la quantité, ou `None` si le joueur annule.
+fn choisir_objet(inventaire: &Inventaire, titre: &str) -> Option<(usize, u8)> {
+    if inventaire.objets.is_empty() {
+        println!("📦 Rien à transférer.");
+        return None;
+    }
+    let mut message = format!("{}\n", titre);
+    for (index, objet) in inventaire.objets.iter().enumerate() {
+        message.push_str(&format!("  {} : {} (x{})\n", index + 1, nom_exemplaire(objet), objet.nombre));
+    }
+    let choix = affichage::faire_choix(&message, &(1..=inventaire.objets.len()).map(|i| i.to_string()).collect());
+    let index = choix.parse::<usize>().ok()? - 1;
+    let quantite = lire_nombre(&format!("Combien ? (max {})", inventaire.objets[index].nombre))?;
+    Some((index, u8::try_from(quantite).ok()?))
+}
+
+/// Ouvre la réserve du joueur : dépôts et retraits d'objets et d'argent,
+/// et agrandissement, jusqu'à ce qu'il la referme.
+///
+/// # Arguments
+/// * `joueur` - Le personnage du joueur, qui porte sa réserve dans sa progression.
+/// * `zone` - La zone où se trouve la réserve.
+/// * `pnjs` - Tous les PNJs du jeu.
+pub fn ouvrir_reserve(joueur: &mut Personnage, zone: &Zone, pnjs: &[PNJ]) {
+    loop {
+        let mut reserve = joueur.progression.reserve.clone();
+        println!("\n=== 🏦 Votre réserve ===");
+        println!("Emplacements : {}/{} | Argent déposé : {} | Votre argent : {}",
+            reserve.inventaire.objets.len(), reserve.inventaire.taille, reserve.argent, joueur.argent);
+        print!("{}", reserve.inventaire);
+        let agrandir = match reserve.prix_amelioration() {
+            Some(prix) => format!("5. Agrandir la réserve (+{} emplacements, {} pièces)", EMPLACEMENTS_PAR_NIVEAU, prix),
+            None => "5. Agrandir la réserve (taille maximale atteinte)".to_string(),
+        };
+        let message = format!(
+            "1. Déposer un objet\n2. Retirer un objet\n3. Déposer de l'argent\n4. Retirer de l'argent\n{}\nq. Refermer la réserve",
+            agrandir
+        );
+        let choix = affichage::faire_choix(&message, &(1..=5).map(|i| i.to_string()).collect());
+        let resultat = match choix.as_str() {
+            "1" => match choisir_objet(&joueur.inventaire, "Quel objet déposer ?") {
+                Some((index, quantite)) => {
+                    let nom = nom_exemplaire(&joueur.inventaire.objets[index]);
+                    reserve.deposer_objet(joueur, index, quantite)
+                        .map(|()| format!("✅ Vous déposez {} x{}.", nom, quantite))
+                }
+                None => continue,
+            },
+            "2" => match choisir_objet(&reserve.inventaire, "Quel objet retirer ?") {
+                Some((index, quantite)) => {
+                    let nom = nom_exemplaire(&reserve.inventaire.objets[index]);
+                    reserve.retirer_objet(joueur, index, quantite)
+                        .map(|()| format!("✅ Vous récupérez {} x{}.", nom, quantite))
+                }
+                None => continue,
+            },
+            "3" => match lire_nombre("Combien déposer ?") {
+                Some(montant) => reserve.deposer_argent(joueur, montant)
+                    .map(|()| format!("💰 Vous déposez {} pièces.", montant)),
+                None => continue,
+            },
+            "4" => match lire_nombre("Combien retirer ?") {
+                Some(montant) => reserve.retirer_argent(joueur, montant)
+                    .map(|()| format!("💰 Vous retirez {} pièces.", montant)),
+                None => continue,
+            },
+            "5" => reserve.ameliorer(joueur)
+                .map(|taille| format!("🔨 Votre réserve compte désormais {} emplacements.", taille)),
+            _ => {
+                affichage::notifier(zone, "Vous refermez votre réserve.", pnjs);
+                return;
+            }
+        };
+        joueur.progression.reserve = reserve;
+        match resultat {
+            Ok(msg) => println!("{}", msg),
+            Err(e) => println!("❌ {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventaire::ObjetInventaire;
+    use crate::outils_test::{catalogue_test, joueur_test, PIERRE};
+
+    /// Crée un joueur portant trois pierres et la somme indiquée.
+    fn joueur_pierres(argent: u32) -> Personnage {
+        let mut joueur = joueur_test(vec![ObjetInventaire { nombre: 3, objet_id: PIERRE, instance: None }], 2);
+        joueur.argent = argent;
+        joueur
+    }
+
+    /// Teste les dépôts et retraits d'objets et d'argent.
+    #[test]
+    fn test_depots_et_retraits() {
+        catalogue_test();
+        let mut joueur = joueur_pierres(100);
+        let mut reserve = Reserve::default();
+
+        assert!(reserve.deposer_objet(&mut joueur, 0, 4).is_err());
+        reserve.deposer_objet(&mut joueur, 0, 2).unwrap();
+        assert_eq!(joueur.inventaire.objets[0].nombre, 1);
+        assert_eq!(reserve.inventaire.objets[0].nombre, 2);
+        reserve.retirer_objet(&mut joueur, 0, 1).unwrap();
+        assert_eq!(joueur.inventaire.objets[0].nombre, 2);
+        assert!(reserve.retirer_objet(&mut joueur, 0, 2).is_err());
+
+        assert!(reserve.deposer_argent(&mut joueur, 150).is_err());
+        reserve.deposer_argent(&mut joueur, 60).unwrap();
+        assert_eq!((joueur.argent, reserve.argent), (40, 60));
+        reserve.retirer_argent(&mut joueur, 10).unwrap();
+        assert_eq!((joueur.argent, reserve.argent), (50, 50));
+    }
+
+    /// Teste l'agrandissement de la réserve jusqu'à sa taille maximale.
+    #[test]
+    fn test_agrandissement() {
+        let mut joueur = joueur_pierres(10_000);
+        let mut reserve = Reserve::default();
+        assert_eq!(reserve.prix_amelioration(), Some(PRIX_AMELIORATION_BASE));
+        assert_eq!(reserve.ameliorer(&mut joueur), Ok(TAILLE_RESERVE_INITIALE + EMPLACEMENTS_PAR_NIVEAU));
+        assert_eq!(reserve.prix_amelioration(), Some(2 * PRIX_AMELIORATION_BASE));
+        while reserve.prix_amelioration().is_some() {
+            reserve.ameliorer(&mut joueur).unwrap();
+        }
+        assert_eq!(reserve.niveau, NIVEAU_MAX);
+        assert!(reserve.ameliorer(&mut joueur).is_err());
+        assert_eq!(joueur.argent, 10_000 - PRIX_AMELIORATION_BASE * (2u32.pow(NIVEAU_MAX as u32) - 1));
+    }
+}
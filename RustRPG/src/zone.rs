@@ -1,16 +1,112 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use rand::Rng;
 use crate::coffre::Coffre;
 use crate::inventaire::Inventaire;
 use crate::affichage::ajouter_notification;
+use crate::personnage::Personnage;
+use crate::objet::{Objet, TypeObjet, OBJETS_DISPONIBLES};
+use crate::quete::nom_quete;
+use crate::personnage::Mob;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
+use rand::prelude::IndexedRandom;
+
+/// Délai (en minutes) avant qu'un mob réapparaisse dans une zone.
+const DELAI_REAPPARITION: i64 = 5;
+/// Probabilité qu'un mob qui poursuit le joueur le suive dans la zone voisine.
+const CHANCE_POURSUITE: f64 = 0.5;
+/// Délai (en minutes) entre deux versements des revenus d'une zone achetée.
+const PERIODE_REVENU: i64 = 10;
+/// Part du prix d'une zone achetée qu'elle rapporte à chaque période.
+const TAUX_REVENU: f64 = 0.05;
+/// Nombre maximal de périodes de revenus accumulées pendant l'absence du joueur.
+const PERIODES_REVENU_MAX: i64 = 12;
 
 /// Représente une connexion entre deux zones.
 ///
-/// Une connexion contient une direction (comme "nord") et l'identifiant
-/// de la zone destination.
-#[derive(Debug, Deserialize, Clone)]
+/// Une connexion contient une direction (comme "nord"), l'identifiant
+/// de la zone destination et les exigences à remplir pour l'emprunter.
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Connexion {
     pub direction: String,
     pub id_dest: String,
+    /// Exigences à remplir pour emprunter la connexion.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exigences: Vec<Exigence>,
+}
+
+/// Condition à remplir pour emprunter une connexion, déclarée dans `zone.json`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum Exigence {
+    /// Le joueur doit posséder l'objet (une clé), qui n'est pas consommé.
+    Cle { objet_id: u8 },
+    /// Le drapeau d'histoire doit être posé.
+    Drapeau { nom: String },
+    /// La quête doit avoir été rendue.
+    QueteTerminee { quete_id: u32 },
+    /// Le joueur doit avoir au moins ce niveau.
+    NiveauMin { niveau: u32 },
+    /// Passage à sens unique : on ne peut l'emprunter que depuis l'autre côté,
+    /// cette connexion n'est jamais praticable.
+    SensUnique,
+}
+
+impl Exigence {
+    /// Vérifie si le joueur remplit l'exigence.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage qui veut emprunter la connexion.
+    ///
+    /// # Retour
+    /// `Err` avec l'explication à afficher si l'exigence n'est pas remplie.
+    pub fn verifier(&self, joueur: &Personnage) -> Result<(), String> {
+        match self {
+            Exigence::Cle { objet_id } => {
+                if joueur.inventaire.objets.iter().any(|o| o.objet_id == *objet_id) {
+                    return Ok(());
+                }
+                let nom = OBJETS_DISPONIBLES.read().unwrap().get(objet_id)
+                    .map(|o| o.nom.clone())
+                    .unwrap_or_else(|| format!("objet {}", objet_id));
+                Err(format!("🔒 Le passage est verrouillé, il vous faut : {}.", nom))
+            }
+            Exigence::Drapeau { nom } => {
+                if joueur.progression.a_drapeau(nom) {
+                    Ok(())
+                } else {
+                    Err("🚧 Le passage est bloqué, vous n'avez pas encore de raison d'y aller.".to_string())
+                }
+            }
+            Exigence::QueteTerminee { quete_id } => {
+                if joueur.progression.quete_terminee(*quete_id) {
+                    Ok(())
+                } else {
+                    Err(format!("🚧 Le passage s'ouvrira après la quête « {} ».", nom_quete(*quete_id)))
+                }
+            }
+            Exigence::NiveauMin { niveau } => {
+                if joueur.progression.niveau >= *niveau {
+                    Ok(())
+                } else {
+                    Err(format!("⛰️ Ce chemin est trop dangereux, il faut être au moins niveau {} (vous êtes niveau {}).", niveau, joueur.progression.niveau))
+                }
+            }
+            Exigence::SensUnique => Err("↩️ Ce passage est à sens unique, impossible de l'emprunter dans ce sens.".to_string()),
+        }
+    }
+}
+
+impl Connexion {
+    /// Vérifie toutes les exigences de la connexion.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage qui veut emprunter la connexion.
+    ///
+    /// # Retour
+    /// `Err` avec l'explication de la première exigence non remplie.
+    pub fn verifier_acces(&self, joueur: &Personnage) -> Result<(), String> {
+        self.exigences.iter().try_for_each(|e| e.verifier(joueur))
+    }
 }
 
 /// Représente une zone dans le jeu, incluant ses connexions, ses coffres,
@@ -31,13 +127,43 @@ pub struct Zone {
     pub coffres: Vec<Coffre>,
     /// Inventaire contenant les objets de la zone.
     pub objet_zone : Inventaire,
-    /// Indique si un monstre (mob) est présent dans la zone.
+    /// Indique si des mobs peuplent la zone : ils y réapparaissent après avoir été tués.
     pub mob_present: bool,
+    /// Identifiant du mob actuellement présent dans la zone.
+    pub mob: Option<u32>,
+    /// Date à partir de laquelle un nouveau mob peut apparaître.
+    pub reapparition: Option<DateTime<Utc>>,
     /// Prix pour débloquer ou accéder à la zone.
     pub prix: u32,
+    /// Nombre de fouilles infructueuses, chacune facilitant la suivante.
+    pub tentatives_fouille: u8,
+    /// Météo actuelle de la zone, changée par les événements aléatoires.
+    pub meteo: Option<String>,
 }
 
 impl Zone {
+    /// Identifiants des zones auxquelles mènent les sorties de la zone.
+    pub fn destinations(&self) -> Vec<u8> {
+        self.connection.iter().filter_map(|c| c.id_dest.parse().ok()).collect()
+    }
+
+    /// Nom de la zone tel que le joueur le connaît : masqué tant qu'il ne l'a pas visitée.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage du joueur.
+    pub fn nom_pour(&self, joueur: &Personnage) -> String {
+        if joueur.progression.a_visite(self.id) {
+            self.nom.clone()
+        } else {
+            "inconnue".to_string()
+        }
+    }
+
+    /// Revenu rapporté à chaque période par la zone à son propriétaire.
+    pub fn revenu(&self) -> u32 {
+        (self.prix as f64 * TAUX_REVENU) as u32
+    }
+
     /// Compte le nombre de coffres visibles dans la zone.
     ///
     /// # Retour
@@ -52,19 +178,74 @@ impl Zone {
         cpt
     }
 
-    /// Révèle tous les coffres non visibles de la zone.
+    /// Cherche les coffres non visibles de la zone.
     ///
-    /// Met à jour l'état des coffres et affiche une notification avec le
-    /// nombre de coffres découverts.
-    pub fn fouiller_zone(&mut self) {
-        let mut cpt :u8 = 0;
-        for coffre in &mut self.coffres {
-            if !coffre.visible {
-                cpt += 1;
-                coffre.visible = true;
+    /// Chaque coffre caché est découvert selon `Coffre::chance_decouverte` ; une
+    /// fouille infructueuse rend la suivante plus facile. Un coffre qui exige un
+    /// outil n'est jamais trouvé sans lui, mais le joueur en est averti.
+    ///
+    /// # Arguments
+    /// * `joueur` - Le personnage qui fouille.
+    ///
+    /// # Retour
+    /// Le nombre de coffres découverts.
+    pub fn fouiller_zone(&mut self, joueur: &Personnage) -> usize {
+        let mut rng = rand::rng();
+        let mut cpt = 0usize;
+        let mut outil_manquant = false;
+        let tentatives = self.tentatives_fouille;
+        for coffre in self.coffres.iter_mut().filter(|c| !c.visible) {
+            if !rng.random_bool(coffre.chance_decouverte(joueur.attributs.perception, tentatives)) {
+                continue;
+            }
+            match coffre.outil {
+                Some(outil) if !joueur.a_sur_lui(outil) => {
+                    outil_manquant = true;
+                }
+                _ => {
+                    coffre.visible = true;
+                    cpt += 1;
+                }
             }
         }
-        ajouter_notification(&("Vous avez trouvé ".to_owned() + &cpt.to_string() + " coffre(s) ."));
+        if cpt == 0 {
+            self.tentatives_fouille = self.tentatives_fouille.saturating_add(1);
+            ajouter_notification("Vous n'avez rien trouvé.");
+        } else {
+            self.tentatives_fouille = 0;
+            ajouter_notification(&format!("Vous avez trouvé {} coffre(s).", cpt));
+        }
+        if outil_manquant {
+            ajouter_notification("Quelque chose semble caché dans l'obscurité, il vous faudrait un outil pour le trouver.");
+        }
+        cpt
+    }
+
+    /// Retire le mob de la zone ; un autre réapparaîtra après `DELAI_REAPPARITION` minutes.
+    ///
+    /// # Arguments
+    /// * `maintenant` - Date courante.
+    pub fn retirer_mob(&mut self, maintenant: DateTime<Utc>) {
+        self.mob = None;
+        self.reapparition = Some(maintenant + chrono::Duration::minutes(DELAI_REAPPARITION));
+    }
+
+    /// Fait apparaître un mob au hasard si la zone en est peuplée, qu'aucun n'y est
+    /// présent et que le délai de réapparition est écoulé.
+    ///
+    /// # Arguments
+    /// * `mobs` - Les mobs du jeu.
+    /// * `maintenant` - Date courante.
+    ///
+    /// # Retour
+    /// `true` si un mob est apparu.
+    pub fn faire_reapparaitre(&mut self, mobs: &[Mob], maintenant: DateTime<Utc>) -> bool {
+        if !self.mob_present || self.mob.is_some() || self.reapparition.is_some_and(|date| date > maintenant) {
+            return false;
+        }
+        self.mob = mobs.choose(&mut rand::rng()).map(|m| m.personnage.id);
+        self.reapparition = None;
+        self.mob.is_some()
     }
 
     /// Supprime un coffre de la zone à l'index spécifié.
@@ -76,29 +257,330 @@ impl Zone {
     }
 }
 
+/// Fait suivre le joueur par le mob de la zone qu'il vient de quitter, si ce mob
+/// poursuit ses proies et que la zone d'arrivée n'a pas déjà le sien.
+///
+/// # Arguments
+/// * `zones` - Toutes les zones du jeu.
+/// * `depart` - Index de la zone quittée.
+/// * `arrivee` - Index de la zone d'arrivée.
+/// * `mobs` - Les mobs du jeu.
+///
+/// # Retour
+/// L'identifiant du mob qui a suivi le joueur.
+pub fn poursuivre_joueur(zones: &mut [Zone], depart: usize, arrivee: usize, mobs: &[Mob]) -> Option<u32> {
+    let mob_id = zones[depart].mob?;
+    let poursuit = mobs.iter()
+        .find(|m| m.personnage.id == mob_id)
+        .is_some_and(|m| m.agressivite.poursuit());
+    if depart == arrivee || zones[arrivee].mob.is_some() || !poursuit || !rand::rng().random_bool(CHANCE_POURSUITE) {
+        return None;
+    }
+    zones[depart].retirer_mob(Utc::now());
+    zones[arrivee].mob = Some(mob_id);
+    Some(mob_id)
+}
+
+/// Verse au joueur les revenus de ses zones achetées pour les périodes écoulées
+/// depuis le dernier versement, dans la limite de `PERIODES_REVENU_MAX`.
+///
+/// # Arguments
+/// * `zones` - Toutes les zones du jeu.
+/// * `joueur` - Le personnage du joueur.
+/// * `maintenant` - Date courante.
+///
+/// # Retour
+/// La somme versée.
+pub fn percevoir_revenus(zones: &[Zone], joueur: &mut Personnage, maintenant: DateTime<Utc>) -> u32 {
+    let Some(dernier) = joueur.progression.dernier_revenu else {
+        joueur.progression.dernier_revenu = Some(maintenant);
+        return 0;
+    };
+    let periodes = (maintenant - dernier).num_minutes() / PERIODE_REVENU;
+    if periodes <= 0 {
+        return 0;
+    }
+    let revenu_par_periode: u32 = zones.iter()
+        .filter(|z| joueur.progression.possede_zone(z.id))
+        .map(Zone::revenu)
+        .sum();
+    let montant = revenu_par_periode * periodes.min(PERIODES_REVENU_MAX) as u32;
+    joueur.ajouter_argent(montant);
+    joueur.progression.dernier_revenu = Some(dernier + chrono::Duration::minutes(periodes * PERIODE_REVENU));
+    montant
+}
+
+/// Renvoie les zones vers lesquelles une clé ouvre un passage, d'après les
+/// exigences `Exigence::Cle` des connexions.
+///
+/// # Arguments
+/// * `zones` - Toutes les zones du jeu.
+/// * `objet_id` - Identifiant de la clé.
+pub fn passages_ouverts_par(zones: &[Zone], objet_id: u8) -> Vec<u8> {
+    let mut destinations: Vec<u8> = zones.iter()
+        .flat_map(|z| z.connection.iter())
+        .filter(|c| c.exigences.contains(&Exigence::Cle { objet_id }))
+        .filter_map(|c| c.id_dest.parse().ok())
+        .collect();
+    destinations.sort();
+    destinations.dedup();
+    destinations
+}
+
+/// Vérifie que la cible de chaque clé est la zone que `zone.json` verrouille
+/// avec elle, et que seules des clés verrouillent des passages.
+///
+/// # Arguments
+/// * `zones` - Toutes les zones du jeu.
+/// * `objets` - Le catalogue des objets.
+///
+/// # Retour
+/// `Err` décrivant la première incohérence trouvée.
+pub fn verifier_cles(zones: &[Zone], objets: &HashMap<u8, Objet>) -> Result<(), String> {
+    let exigees = zones.iter()
+        .flat_map(|z| z.connection.iter())
+        .flat_map(|c| c.exigences.iter())
+        .filter_map(|e| match e {
+            Exigence::Cle { objet_id } => Some(*objet_id),
+            _ => None,
+        });
+    for objet_id in exigees {
+        if !matches!(objets.get(&objet_id).map(|o| &o.objet_type), Some(TypeObjet::Cle { .. })) {
+            return Err(format!("L'objet {} verrouille un passage mais n'est pas une clé", objet_id));
+        }
+    }
+    let mut cles: Vec<&Objet> = objets.values().filter(|o| matches!(o.objet_type, TypeObjet::Cle { .. })).collect();
+    cles.sort_by_key(|o| o.id);
+    for cle in cles {
+        let TypeObjet::Cle { cible } = cle.objet_type else { continue };
+        let passages = passages_ouverts_par(zones, cle.id);
+        if passages != cible.into_iter().collect::<Vec<u8>>() {
+            let attendue = cible.map_or("aucun passage".to_string(), |zone_id| format!("la zone {}", zone_id));
+            return Err(format!("La clé {} ({}) doit ouvrir {}, mais zone.json la demande vers les zones {:?}",
+                cle.id, cle.nom, attendue, passages));
+        }
+    }
+    Ok(())
+}
+
+/// Cherche le plus court chemin vers une zone en ne traversant que des zones
+/// ouvertes et déjà visitées, par des connexions dont le joueur remplit les exigences.
+///
+/// # Arguments
+/// * `zones` - Toutes les zones du jeu.
+/// * `depart` - Index de la zone où se trouve le joueur.
+/// * `arrivee` - Identifiant de la zone à atteindre.
+/// * `joueur` - Le personnage du joueur.
+///
+/// # Retour
+/// Les index des zones traversées, zone d'arrivée comprise et zone de départ exclue,
+/// ou `None` si aucun chemin connu n'y mène.
+pub fn chemin_le_plus_court(zones: &[Zone], depart: usize, arrivee: u8, joueur: &Personnage) -> Option<Vec<usize>> {
+    let mut precedents: HashMap<usize, usize> = HashMap::new();
+    let mut a_traiter = VecDeque::from([depart]);
+
+    while let Some(index) = a_traiter.pop_front() {
+        if zones[index].id == arrivee {
+            let mut chemin = vec![index];
+            while let Some(&precedent) = precedents.get(chemin.last()?) {
+                chemin.push(precedent);
+            }
+            chemin.pop();
+            chemin.reverse();
+            return Some(chemin);
+        }
+        for conn in &zones[index].connection {
+            let Some(suivant) = zones.iter().position(|z| conn.id_dest == z.id.to_string()) else {
+                continue;
+            };
+            let praticable = zones[suivant].ouvert
+                && joueur.progression.a_visite(zones[suivant].id)
+                && conn.verifier_acces(joueur).is_ok();
+            if praticable && suivant != depart && !precedents.contains_key(&suivant) {
+                precedents.insert(suivant, index);
+                a_traiter.push_back(suivant);
+            }
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::coffre::Coffre;
+    use crate::inventaire::ObjetInventaire;
+    use crate::outils_test::{joueur_test, zone_test};
 
     /// Teste la méthode `compter_coffre` pour vérifier que seuls les coffres visibles sont comptés.
     #[test]
     fn test_compter_coffre() {
         let coffres = vec![
-            Coffre { _id: 1, _id_zone: 1, ouvert: true, _description: "C1".to_string(), inventaire: Inventaire { taille: 1, objets: vec![] }, visible: true },
-            Coffre { _id: 2, _id_zone: 1, ouvert: true, _description: "C2".to_string(), inventaire: Inventaire { taille: 1, objets: vec![] }, visible: false },
+            Coffre { _id: 1, _id_zone: 1, ouvert: true, _description: "C1".to_string(), inventaire: Inventaire { taille: 1, objets: vec![] }, visible: true, difficulte: 0, outil: None, serrure: 0, cle: 12, piege: 0 },
+            Coffre { _id: 2, _id_zone: 1, ouvert: true, _description: "C2".to_string(), inventaire: Inventaire { taille: 1, objets: vec![] }, visible: false, difficulte: 0, outil: None, serrure: 0, cle: 12, piege: 0 },
         ];
-        let zone = Zone {
-            id: 1,
-            nom: "TestZone".to_string(),
-            ouvert: true,
-            description: "desc".to_string(),
-            connection: vec![],
-            coffres,
-            objet_zone: Inventaire { taille: 1, objets: vec![] },
-            mob_present: false,
-            prix: 0,
-        };
+        let zone = Zone { coffres, ..zone_test(1) };
         assert_eq!(zone.compter_coffre(), 1);
     }
-}
\ No newline at end of file
+
+    /// Crée un joueur de test avec la perception et les objets indiqués.
+    fn joueur_perceptif(perception: u8, objets: Vec<ObjetInventaire>) -> Personnage {
+        let mut joueur = joueur_test(objets, 5);
+        joueur.attributs.perception = perception;
+        joueur
+    }
+
+    /// Teste qu'une perception maximale révèle les coffres faciles, mais pas
+    /// ceux qui exigent un outil que le joueur ne porte ni dans son inventaire ni équipé.
+    #[test]
+    fn test_fouiller_zone_perception() {
+        let mut zone = Zone {
+            coffres: vec![
+                Coffre { _id: 1, _id_zone: 1, ouvert: true, _description: "C1".to_string(), inventaire: Inventaire { taille: 1, objets: vec![] }, visible: false, difficulte: 0, outil: None, serrure: 0, cle: 12, piege: 0 },
+                Coffre { _id: 2, _id_zone: 1, ouvert: true, _description: "C2".to_string(), inventaire: Inventaire { taille: 1, objets: vec![] }, visible: false, difficulte: 50, outil: None, serrure: 0, cle: 12, piege: 0 },
+                Coffre { _id: 3, _id_zone: 1, ouvert: true, _description: "C3".to_string(), inventaire: Inventaire { taille: 1, objets: vec![] }, visible: false, difficulte: 0, outil: Some(13), serrure: 0, cle: 12, piege: 0 },
+            ],
+            ..zone_test(1)
+        };
+        assert_eq!(zone.fouiller_zone(&joueur_perceptif(100, vec![])), 2);
+        assert_eq!(zone.compter_coffre(), 2);
+
+        assert_eq!(zone.fouiller_zone(&joueur_perceptif(100, vec![])), 0);
+        assert_eq!(zone.tentatives_fouille, 1);
+
+        let lampe = ObjetInventaire { objet_id: 13, nombre: 1, instance: None };
+        assert_eq!(zone.fouiller_zone(&joueur_perceptif(100, vec![lampe.clone()])), 1);
+        assert_eq!(zone.compter_coffre(), 3);
+        assert_eq!(zone.tentatives_fouille, 0);
+
+        // Une lampe tenue en main compte autant qu'une lampe rangée
+        zone.coffres[2].visible = false;
+        let mut joueur = joueur_perceptif(100, vec![]);
+        joueur.parties_du_corps = vec![crate::personnage::PartieDuCorps::new("Bras droit".to_string(), 75)];
+        joueur.parties_du_corps[0].ajouter_equipement(lampe).unwrap();
+        assert_eq!(zone.fouiller_zone(&joueur), 1);
+    }
+
+    /// Teste que chaque exigence de connexion bloque le joueur qui ne la remplit pas.
+    #[test]
+    fn test_exigences_connexion() {
+        let connexion = Connexion {
+            direction: "nord".to_string(),
+            id_dest: "2".to_string(),
+            exigences: vec![
+                Exigence::NiveauMin { niveau: 2 },
+                Exigence::Cle { objet_id: 15 },
+                Exigence::Drapeau { nom: "porte_ouverte".to_string() },
+            ],
+        };
+        let mut joueur = joueur_perceptif(50, vec![]);
+        assert!(connexion.verifier_acces(&joueur).unwrap_err().contains("niveau 2"));
+        joueur.progression.niveau = 2;
+        assert!(connexion.verifier_acces(&joueur).unwrap_err().contains("verrouillé"));
+        joueur.inventaire.objets.push(ObjetInventaire { objet_id: 15, nombre: 1, instance: None });
+        assert!(connexion.verifier_acces(&joueur).is_err());
+        joueur.progression.poser_drapeau("porte_ouverte");
+        assert!(connexion.verifier_acces(&joueur).is_ok());
+        assert!(Exigence::SensUnique.verifier(&joueur).is_err());
+    }
+
+    /// Teste que les passages d'une clé se déduisent des exigences des connexions
+    /// et doivent correspondre à sa cible.
+    #[test]
+    fn test_passages_des_cles() {
+        let connexion = Connexion {
+            direction: "nord".to_string(),
+            id_dest: "2".to_string(),
+            exigences: vec![Exigence::Cle { objet_id: 15 }],
+        };
+        let zones = vec![
+            Zone { connection: vec![connexion.clone()], ..zone_test(1) },
+            Zone { connection: vec![Connexion { id_dest: "3".to_string(), ..connexion.clone() }], ..zone_test(4) },
+            Zone { connection: vec![Connexion { exigences: vec![], ..connexion }], ..zone_test(5) },
+        ];
+        assert_eq!(passages_ouverts_par(&zones, 15), vec![2, 3]);
+        assert!(passages_ouverts_par(&zones, 12).is_empty());
+
+        let objet = |id: u8, objet_type: TypeObjet| (id, Objet { id, nom: "Clé".to_string(), poids: 1, prix: 1, objet_type });
+        let mut objets = HashMap::from([objet(12, TypeObjet::Cle { cible: None }), objet(15, TypeObjet::Cle { cible: Some(2) })]);
+        // La clé 15 verrouille aussi le passage vers la zone 3
+        assert!(verifier_cles(&zones, &objets).unwrap_err().contains("La clé 15"));
+        assert!(verifier_cles(&zones[..1], &objets).is_ok());
+        objets.insert(15, objet(15, TypeObjet::Quete).1);
+        assert!(verifier_cles(&zones[..1], &objets).unwrap_err().contains("n'est pas une clé"));
+    }
+
+    /// Teste la réapparition d'un mob après le délai et sa poursuite du joueur.
+    #[test]
+    fn test_reapparition_et_poursuite() {
+        let json = r#"{ "id": 7, "nom": "Loup", "description": "desc", "force": 50,
+            "inventaire": { "taille": 1, "objets": [] }, "parties_du_corps": [],
+            "argent": 0, "est_vivant": true }"#;
+        let mob: Mob = serde_json::from_str(json).unwrap();
+        assert!(mob.agressivite.poursuit());
+        let mut zones = vec![zone_test(1), zone_test(2)];
+        zones[0].mob_present = true;
+
+        let maintenant = Utc::now();
+        assert!(zones[0].faire_reapparaitre(std::slice::from_ref(&mob), maintenant));
+        assert_eq!(zones[0].mob, Some(7));
+        zones[0].retirer_mob(maintenant);
+        assert!(!zones[0].faire_reapparaitre(std::slice::from_ref(&mob), maintenant));
+        assert!(zones[0].faire_reapparaitre(std::slice::from_ref(&mob), maintenant + chrono::Duration::minutes(DELAI_REAPPARITION)));
+        assert!(!zones[1].faire_reapparaitre(std::slice::from_ref(&mob), maintenant));
+
+        // Un mob pacifique ne suit pas le joueur
+        let mut paisible = mob.clone();
+        paisible.agressivite = crate::personnage::Agressivite::Pacifique;
+        assert_eq!(poursuivre_joueur(&mut zones, 0, 1, &[paisible]), None);
+        // Un mob agressif le suit parfois, mais jamais dans une zone déjà occupée
+        zones[1].mob = Some(3);
+        assert_eq!(poursuivre_joueur(&mut zones, 0, 1, std::slice::from_ref(&mob)), None);
+        assert_eq!(zones[0].mob, Some(7));
+    }
+
+    /// Teste le voyage rapide : chemin le plus court, en évitant les zones
+    /// fermées ou jamais visitées.
+    #[test]
+    fn test_chemin_le_plus_court() {
+        let relier = |zone: &mut Zone, direction: &str, id: u8| zone.connection.push(Connexion {
+            direction: direction.to_string(),
+            id_dest: id.to_string(),
+            exigences: vec![],
+        });
+        let mut zones: Vec<Zone> = (1..=4).map(zone_test).collect();
+        relier(&mut zones[0], "nord", 2);
+        relier(&mut zones[1], "nord", 3);
+        relier(&mut zones[0], "est", 4);
+        relier(&mut zones[3], "nord", 3);
+        let mut joueur = joueur_test(vec![], 5);
+        joueur.progression.zones_visitees = vec![1, 2, 3, 4];
+
+        assert_eq!(chemin_le_plus_court(&zones, 0, 3, &joueur).map(|c| c.len()), Some(2));
+        assert_eq!(chemin_le_plus_court(&zones, 0, 1, &joueur), Some(vec![]));
+        zones[1].ouvert = false;
+        assert_eq!(chemin_le_plus_court(&zones, 0, 3, &joueur), Some(vec![3, 2]));
+        joueur.progression.zones_visitees = vec![1, 2, 3];
+        assert_eq!(chemin_le_plus_court(&zones, 0, 3, &joueur), None);
+    }
+
+    /// Teste le versement des revenus des zones achetées.
+    #[test]
+    fn test_percevoir_revenus() {
+        let mut zones = vec![zone_test(1), zone_test(2)];
+        zones[0].prix = 200;
+        zones[1].prix = 1000;
+        let mut joueur = joueur_test(vec![], 5);
+        joueur.progression.acheter_zone(1);
+        let debut = Utc::now();
+
+        assert_eq!(percevoir_revenus(&zones, &mut joueur, debut), 0);
+        assert_eq!(percevoir_revenus(&zones, &mut joueur, debut + chrono::Duration::minutes(PERIODE_REVENU - 1)), 0);
+        assert_eq!(percevoir_revenus(&zones, &mut joueur, debut + chrono::Duration::minutes(2 * PERIODE_REVENU + 3)), 20);
+        assert_eq!(joueur.argent, 20);
+        // Les minutes restantes comptent pour la période suivante, et l'absence est plafonnée
+        joueur.progression.acheter_zone(2);
+        let longtemps = debut + chrono::Duration::minutes(100 * PERIODE_REVENU);
+        assert_eq!(percevoir_revenus(&zones, &mut joueur, longtemps), 60 * PERIODES_REVENU_MAX as u32);
+    }
+}